@@ -0,0 +1,269 @@
+//! Measures the per-frame cost of the layout systems for 10,000 loaded images
+//! that aren't changing, which should be close to that of an app without any,
+//! and the cost of extracting them for rendering, which is paid every frame.
+//!
+//! The images are drawn from textures, named atlas frames, descriptors and layers,
+//! so the atlas checks, name resolution and descriptors are measured too.
+//!
+//! `cargo bench --bench layout`
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+use std::time::Instant;
+
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::render::primitives::Frustum;
+use bevy::render::render_resource::Extent3d;
+use bevy::render::render_resource::TextureDimension;
+use bevy::render::render_resource::TextureFormat;
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy::render::view::VisibilityPlugin;
+use bevy::render::view::VisibleEntities;
+use bevy::render::MainWorld;
+use bevy::render::RenderApp;
+use bevy::render::RenderStage;
+use bevy::sprite::ExtractedSprites;
+use bevy::text::TextPlugin;
+use bevy::ui::extract_uinodes;
+use bevy::ui::ExtractedUiNodes;
+use bevy::ui::RenderUiSystem;
+use bevy::ui::UiPlugin;
+use bevy::window::WindowId;
+use bevy::window::WindowPlugin;
+use bevy_ui_exact_image::prelude::*;
+use bevy_ui_exact_image::render::ExtractedExactImages;
+
+const IMAGES: usize = 10_000;
+const WARMUP_FRAMES: u32 = 10;
+const FRAMES: u32 = 200;
+
+fn app(with_plugin: bool) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<Shader>()
+        .add_asset::<Image>()
+        .add_asset::<TextureAtlas>()
+        .add_asset::<AtlasFrameNames>()
+        .add_asset::<ExactImageDescriptor>();
+    if with_plugin {
+        app.add_plugin(ExactImagePlugin);
+    }
+    app
+}
+
+/// An app that lays out the ui and runs the extract stage of a render sub-app without a gpu,
+/// like the test harness, along with the total time spent in the extract stage
+fn ui_app(with_plugin: bool) -> (App, Rc<Cell<Duration>>) {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(TransformPlugin)
+        .add_plugin(HierarchyPlugin)
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(InputPlugin)
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<Shader>()
+        .add_asset::<Image>()
+        .add_asset::<Mesh>()
+        .add_asset::<TextureAtlas>()
+        .add_asset::<AtlasFrameNames>()
+        .add_asset::<ExactImageDescriptor>()
+        .add_plugin(VisibilityPlugin)
+        .add_plugin(TextPlugin)
+        .add_plugin(UiPlugin);
+
+    let mut render_app = App::empty();
+    render_app
+        .add_stage(RenderStage::Extract, SystemStage::parallel())
+        .init_resource::<ExtractedUiNodes>()
+        .init_resource::<ExtractedSprites>()
+        .add_system_to_stage(
+            RenderStage::Extract,
+            extract_uinodes.label(RenderUiSystem::ExtractNode),
+        );
+    let extract_time = Rc::new(Cell::new(Duration::ZERO));
+    let total = extract_time.clone();
+    app.add_sub_app(RenderApp, render_app, move |app_world, render_app| {
+        let start = Instant::now();
+        extract(app_world, render_app);
+        total.set(total.get() + start.elapsed());
+    });
+    if with_plugin {
+        app.add_plugin(ExactImagePlugin);
+    }
+
+    app.world.resource_mut::<Windows>().add(Window::new(
+        WindowId::primary(),
+        &WindowDescriptor::default(),
+        1280,
+        720,
+        1.,
+        None,
+        None,
+    ));
+    app.world
+        .resource_mut::<Assets<Image>>()
+        .set_untracked(DEFAULT_IMAGE_HANDLE, Image::default());
+    // ui nodes are only visible to cameras
+    app.world.spawn((
+        Camera::default(),
+        VisibleEntities::default(),
+        Frustum::default(),
+    ));
+    (app, extract_time)
+}
+
+/// Runs the extract stage with the main world, like bevy's renderer does
+fn extract(app_world: &mut World, render_app: &mut App) {
+    // these are drained by the exact image renderer, which the bench doesn't have
+    if let Some(mut images) = render_app.world.get_resource_mut::<ExtractedExactImages>() {
+        images.images.clear();
+    }
+    let extract = render_app
+        .schedule
+        .get_stage_mut::<SystemStage>(RenderStage::Extract)
+        .unwrap();
+    let mut main_world = MainWorld::default();
+    std::mem::swap(&mut *main_world, app_world);
+    render_app.world.insert_resource(main_world);
+    extract.run(&mut render_app.world);
+    let mut main_world = render_app.world.remove_resource::<MainWorld>().unwrap();
+    std::mem::swap(&mut *main_world, app_world);
+    extract.apply_buffers(&mut render_app.world);
+}
+
+fn spawn_images(app: &mut App) {
+    let texture = app
+        .world
+        .resource_mut::<Assets<Image>>()
+        .add(Image::new_fill(
+            Extent3d {
+                width: 16,
+                height: 16,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[255, 255, 255, 255],
+            TextureFormat::Rgba8UnormSrgb,
+        ));
+    let atlas = app
+        .world
+        .resource_mut::<Assets<TextureAtlas>>()
+        .add(TextureAtlas::from_grid(
+            texture.clone(),
+            Vec2::splat(4.),
+            4,
+            4,
+            None,
+            None,
+        ));
+    let names = app
+        .world
+        .resource_mut::<Assets<AtlasFrameNames>>()
+        .add(AtlasFrameNames::new(
+            (0..16).map(|index| (format!("frame_{index}"), index)),
+        ));
+    let descriptor = app
+        .world
+        .resource_mut::<Assets<ExactImageDescriptor>>()
+        .add(ExactImageDescriptor {
+            texture: texture.clone(),
+            rect: Some(Rect::new(0., 0., 8., 8.)),
+            size: ExactSize::Texture,
+            alignment: ImageAlignment::Center,
+            rotation: None,
+            color: Color::WHITE,
+            nine_slice: None,
+            clips: Default::default(),
+        });
+    for i in 0..IMAGES / 4 {
+        app.world.spawn(ExactImageBundle {
+            image: ExactImage {
+                texture: texture.clone(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        app.world.spawn((
+            ExactAtlasImageBundle {
+                image: ExactAtlasImage {
+                    atlas: atlas.clone(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            NamedAtlasFrame::new(names.clone(), format!("frame_{}", i % 16)),
+        ));
+        app.world.spawn(ExactSourceImageBundle {
+            image: ExactSourceImage {
+                source: descriptor.clone().into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        app.world.spawn((
+            NodeBundle::default(),
+            ExactImageLayers::new([ImageLayer {
+                image: LayerImage::Atlas {
+                    atlas: atlas.clone(),
+                    index: i % 16,
+                },
+                ..Default::default()
+            }]),
+        ));
+    }
+}
+
+fn measure(app: &mut App) -> Duration {
+    for _ in 0..WARMUP_FRAMES {
+        app.update();
+    }
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        app.update();
+    }
+    start.elapsed() / FRAMES
+}
+
+/// Mean time per frame spent in the extract stage
+fn measure_extract(with_plugin: bool) -> Duration {
+    let (mut app, extract_time) = ui_app(with_plugin);
+    spawn_images(&mut app);
+    for _ in 0..WARMUP_FRAMES {
+        app.update();
+    }
+    extract_time.set(Duration::ZERO);
+    for _ in 0..FRAMES {
+        app.update();
+    }
+    extract_time.get() / FRAMES
+}
+
+fn main() {
+    let mut baseline = app(false);
+    spawn_images(&mut baseline);
+    let baseline = measure(&mut baseline);
+
+    let mut exact = app(true);
+    spawn_images(&mut exact);
+    let exact = measure(&mut exact);
+
+    println!("{IMAGES} images in steady state, mean over {FRAMES} frames:");
+    println!("  without ExactImagePlugin: {baseline:?} per frame");
+    println!("  with ExactImagePlugin:    {exact:?} per frame");
+    println!(
+        "  layout cost:              {:?} per frame",
+        exact.saturating_sub(baseline)
+    );
+
+    let baseline = measure_extract(false);
+    let exact = measure_extract(true);
+    println!("  extract stage without ExactImagePlugin: {baseline:?} per frame");
+    println!("  extract stage with ExactImagePlugin:    {exact:?} per frame");
+    println!(
+        "  extraction cost:                        {:?} per frame",
+        exact.saturating_sub(baseline)
+    );
+}
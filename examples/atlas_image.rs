@@ -79,6 +79,7 @@ fn spawn_example(
                         size: ExactSize::Texture,
                         alignment,
                         rotation: None,
+                        ..Default::default()
                     },
                     style: Style {
                         size: Size::new(Val::Px(256.), Val::Px(256.)),
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+
+fn spawn_example(mut commands: Commands, assets: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                justify_content: JustifyContent::SpaceEvenly,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: BackgroundColor(Color::DARK_GRAY),
+            ..Default::default()
+        })
+        .with_children(|builder| {
+            for blend_mode in [
+                ImageBlendMode::Alpha,
+                ImageBlendMode::PremultipliedAlpha,
+                ImageBlendMode::Additive,
+                ImageBlendMode::Multiply,
+                ImageBlendMode::Screen,
+            ] {
+                builder.spawn(ExactImageBundle {
+                    image: ExactImage {
+                        texture: assets.load("orientation.png"),
+                        color: Color::rgba(1., 1., 1., 0.75),
+                        size: ExactSize::Exactly(Vec2::splat(128.)),
+                        blend_mode,
+                        ..Default::default()
+                    },
+                    style: Style {
+                        size: Size::new(Val::Px(160.), Val::Px(160.)),
+                        ..Default::default()
+                    },
+                    background_color: BackgroundColor(Color::TEAL),
+                    ..Default::default()
+                });
+            }
+        });
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_plugin(ExactImagePlugin)
+        .add_startup_system(spawn_example)
+        .run();
+}
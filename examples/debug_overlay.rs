@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+
+fn spawn_example(mut commands: Commands, assets: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+    let texture: Handle<Image> = assets.load("orientation.png");
+    let images = [
+        (ExactSize::Texture, ImageAlignment::TopLeft, None),
+        (
+            ExactSize::ForcePreserveAspectRatio,
+            ImageAlignment::Center,
+            None,
+        ),
+        (
+            ExactSize::Scaled(Vec2::splat(2.)),
+            ImageAlignment::BottomRight,
+            Some(0.5),
+        ),
+    ];
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                justify_content: JustifyContent::SpaceEvenly,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(|builder| {
+            for (size, alignment, rotation) in images {
+                builder.spawn(ExactImageBundle {
+                    image: ExactImage {
+                        texture: texture.clone(),
+                        size,
+                        alignment,
+                        rotation,
+                        ..Default::default()
+                    },
+                    style: Style {
+                        size: Size::new(Val::Px(200.), Val::Px(200.)),
+                        ..Default::default()
+                    },
+                    background_color: BackgroundColor(Color::DARK_GRAY),
+                    ..Default::default()
+                });
+            }
+        });
+}
+
+/// Space toggles the overlay
+fn toggle_overlay(keys: Res<Input<KeyCode>>, mut settings: ResMut<ExactImageDebugSettings>) {
+    if keys.just_pressed(KeyCode::Space) {
+        settings.enabled = !settings.enabled;
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_plugin(ExactImagePlugin)
+        .add_plugin(ExactImageDebugPlugin)
+        // set `font` to label each image with its `ExactSize` and size in pixels
+        .insert_resource(ExactImageDebugSettings::default())
+        .add_startup_system(spawn_example)
+        .add_system(toggle_overlay)
+        .run();
+}
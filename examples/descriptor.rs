@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+
+fn spawn_example(mut commands: Commands, assets: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+    // edit assets/orientation.exactimage.ron while the example is running to see the changes
+    let descriptor: Handle<ExactImageDescriptor> = assets.load("orientation.exactimage.ron");
+    commands.spawn(ExactSourceImageBundle {
+        image: ExactSourceImage {
+            source: descriptor.into(),
+            ..Default::default()
+        },
+        style: Style {
+            size: Size::new(Val::Px(400.0), Val::Px(400.0)),
+            ..Default::default()
+        },
+        background_color: BackgroundColor(Color::DARK_GRAY),
+        ..Default::default()
+    });
+}
+
+fn main() {
+    App::new()
+        .add_plugins(
+            DefaultPlugins
+                .set(ImagePlugin::default_nearest())
+                .set(AssetPlugin {
+                    watch_for_changes: true,
+                    ..Default::default()
+                }),
+        )
+        .add_plugin(ExactImagePlugin)
+        .add_startup_system(spawn_example)
+        .run();
+}
@@ -0,0 +1,69 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+
+fn spawn_example(mut commands: Commands, assets: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                justify_content: JustifyContent::SpaceEvenly,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: BackgroundColor(Color::BLACK),
+            ..Default::default()
+        })
+        .with_children(|builder| {
+            for effects in [
+                ImageEffects::default(),
+                ImageEffects {
+                    grayscale: 1.,
+                    ..Default::default()
+                },
+                ImageEffects {
+                    brightness: 0.5,
+                    ..Default::default()
+                },
+                ImageEffects {
+                    contrast: 2.,
+                    ..Default::default()
+                },
+                ImageEffects {
+                    saturation: 2.,
+                    ..Default::default()
+                },
+                ImageEffects {
+                    hue_rotation: PI,
+                    ..Default::default()
+                },
+            ] {
+                builder.spawn((
+                    ExactImageBundle {
+                        image: ExactImage {
+                            texture: assets.load("orientation.png"),
+                            size: ExactSize::Exactly(Vec2::splat(128.)),
+                            color: Color::WHITE,
+                            ..Default::default()
+                        },
+                        style: Style {
+                            size: Size::new(Val::Px(128.), Val::Px(128.)),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    effects,
+                ));
+            }
+        });
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_plugin(ExactImagePlugin)
+        .add_startup_system(spawn_example)
+        .run();
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+
+fn spawn_example(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_assets: ResMut<Assets<TextureAtlas>>,
+) {
+    commands.spawn(Camera2dBundle::default());
+    let texture_atlas = TextureAtlas::from_grid(
+        asset_server.load("orientation_big.png"),
+        Vec2::splat(256.),
+        2,
+        2,
+        None,
+        None,
+    );
+    let texture_atlas_handle = texture_atlas_assets.add(texture_atlas);
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(256.), Val::Px(256.)),
+                ..Default::default()
+            },
+            background_color: BackgroundColor(Color::MIDNIGHT_BLUE),
+            ..Default::default()
+        },
+        ExactImageLayers::new([
+            // frame
+            ImageLayer {
+                image: LayerImage::Atlas {
+                    atlas: texture_atlas_handle.clone(),
+                    index: 0,
+                },
+                size: ExactSize::FillNode,
+                color: Color::rgba(1., 1., 1., 0.5),
+                ..Default::default()
+            },
+            // icon
+            ImageLayer {
+                image: asset_server.load("orientation.png").into(),
+                size: ExactSize::Exactly(Vec2::splat(128.)),
+                color: Color::WHITE,
+                ..Default::default()
+            },
+            // badge
+            ImageLayer {
+                image: LayerImage::Atlas {
+                    atlas: texture_atlas_handle,
+                    index: 3,
+                },
+                size: ExactSize::Exactly(Vec2::splat(64.)),
+                alignment: ImageAlignment::TopRight,
+                color: Color::WHITE,
+                ..Default::default()
+            },
+        ]),
+    ));
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_plugin(ExactImagePlugin)
+        .add_startup_system(spawn_example)
+        .run();
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+
+fn spawn_example(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(|builder| {
+            builder.spawn(ExactImageBundle {
+                image: ExactImage {
+                    // the texture isn't loaded until two seconds after startup
+                    color: Color::WHITE,
+                    loading_placeholder: Some(LoadingPlaceholder::Color(Color::DARK_GRAY)),
+                    // the node keeps this size once the texture has loaded
+                    placeholder_size: Some(Vec2::new(256., 256.)),
+                    ..Default::default()
+                },
+                background_color: BackgroundColor(Color::RED),
+                ..Default::default()
+            });
+        });
+}
+
+fn load_texture(
+    time: Res<Time>,
+    assets: Res<AssetServer>,
+    mut loaded: Local<bool>,
+    mut images: Query<&mut ExactImage>,
+) {
+    if !*loaded && 2. < time.elapsed_seconds() {
+        *loaded = true;
+        for mut image in images.iter_mut() {
+            image.texture = assets.load("orientation_big.png");
+        }
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_plugin(ExactImagePlugin)
+        .add_startup_system(spawn_example)
+        .add_system(load_texture)
+        .run();
+}
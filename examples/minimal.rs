@@ -10,6 +10,7 @@ fn spawn_example(mut commands: Commands, assets: Res<AssetServer>) {
             size: ExactSize::Exactly(Vec2::new(300., 200.)),
             alignment: ImageAlignment::BottomCenter,
             rotation: None,
+            ..Default::default()
         },
         style: Style {
             size: Size::new(Val::Px(400.0), Val::Px(400.0)),
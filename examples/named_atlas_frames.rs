@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(ExactImagePlugin)
+        .add_startup_system(spawn_example)
+        .run();
+}
+
+fn spawn_example(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_assets: ResMut<Assets<TextureAtlas>>,
+    mut frame_names_assets: ResMut<Assets<AtlasFrameNames>>,
+) {
+    commands.spawn(Camera2dBundle::default());
+    let texture_atlas = TextureAtlas::from_grid(
+        asset_server.load("orientation_big.png"),
+        Vec2::splat(256.),
+        2,
+        2,
+        None,
+        None,
+    );
+    let texture_atlas_handle = texture_atlas_assets.add(texture_atlas);
+    let frame_names = frame_names_assets.add(AtlasFrameNames::new([
+        ("top_left", 0),
+        ("top_right", 1),
+        ("bottom_left", 2),
+        ("bottom_right", 3),
+    ]));
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                justify_content: JustifyContent::SpaceEvenly,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: BackgroundColor(Color::BLACK),
+            ..Default::default()
+        })
+        .with_children(|builder| {
+            for name in ["bottom_right", "top_left"] {
+                builder.spawn((
+                    ExactAtlasImageBundle {
+                        image: ExactAtlasImage {
+                            atlas: texture_atlas_handle.clone(),
+                            color: Color::WHITE,
+                            size: ExactSize::Exactly(Vec2::splat(128.)),
+                            ..Default::default()
+                        },
+                        style: Style {
+                            size: Size::new(Val::Px(128.), Val::Px(128.)),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    NamedAtlasFrame::new(frame_names.clone(), name),
+                ));
+            }
+        });
+}
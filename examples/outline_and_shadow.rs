@@ -0,0 +1,70 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+
+fn spawn_example(mut commands: Commands, assets: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                justify_content: JustifyContent::SpaceEvenly,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: BackgroundColor(Color::BEIGE),
+            ..Default::default()
+        })
+        .with_children(|builder| {
+            builder.spawn((
+                ExactImageBundle {
+                    image: ExactImage {
+                        texture: assets.load("orientation.png"),
+                        size: ExactSize::Exactly(Vec2::splat(128.)),
+                        color: Color::WHITE,
+                        ..Default::default()
+                    },
+                    style: Style {
+                        size: Size::new(Val::Px(192.), Val::Px(192.)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ImageOutline {
+                    color: Color::YELLOW,
+                    width: 4.,
+                },
+            ));
+            builder.spawn((
+                ExactImageBundle {
+                    image: ExactImage {
+                        texture: assets.load("orientation.png"),
+                        size: ExactSize::Exactly(Vec2::splat(128.)),
+                        color: Color::WHITE,
+                        alignment: ImageAlignment::TopLeft,
+                        rotation: Some(PI / 6.),
+                        ..Default::default()
+                    },
+                    style: Style {
+                        size: Size::new(Val::Px(192.), Val::Px(192.)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ImageShadow {
+                    offset: Vec2::new(8., 8.),
+                    blur_radius: 6.,
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_plugin(ExactImagePlugin)
+        .add_startup_system(spawn_example)
+        .run();
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+
+fn spawn_example(mut commands: Commands, assets: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+    let texture: Handle<Image> = assets.load("orientation.png");
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                justify_content: JustifyContent::SpaceEvenly,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            // the texture's own sampler, from the default linear `ImagePlugin`
+            parent.spawn_exact_image(
+                ExactImage::new(texture.clone()).size(ExactSize::Scaled(Vec2::splat(4.))),
+            );
+            for sampling in [ImageSampling::Nearest, ImageSampling::LinearMipmapped] {
+                parent
+                    .spawn_exact_image(
+                        ExactImage::new(texture.clone()).size(ExactSize::Scaled(Vec2::splat(4.))),
+                    )
+                    .insert(sampling);
+            }
+        });
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(ExactImagePlugin)
+        .add_startup_system(spawn_example)
+        .run();
+}
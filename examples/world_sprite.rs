@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+
+#[derive(Component)]
+struct Bob;
+
+fn spawn_example(mut commands: Commands, assets: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+    let box_size = Vec2::new(160., 120.);
+    for (i, alignment) in [
+        ImageAlignment::TopLeft,
+        ImageAlignment::Center,
+        ImageAlignment::BottomRight,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        commands
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_xyz(200. * (i as f32 - 1.), 0., 0.)),
+                Bob,
+            ))
+            .with_children(|parent| {
+                // the box the image is aligned within
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::DARK_GRAY,
+                        custom_size: Some(box_size),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                });
+                parent.spawn(ExactSpriteBundle {
+                    sprite: ExactSprite::new(assets.load::<Image, _>("orientation.png"))
+                        .box_size(box_size)
+                        .size(ExactSize::Exactly(Vec2::new(64., 48.)))
+                        .align(alignment),
+                    transform: Transform::from_xyz(0., 0., 1.),
+                    ..Default::default()
+                });
+            });
+    }
+}
+
+fn bob(time: Res<Time>, mut query: Query<&mut Transform, With<Bob>>) {
+    for mut transform in query.iter_mut() {
+        transform.translation.y = 100. * time.elapsed_seconds().sin();
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_plugin(ExactImagePlugin)
+        .add_startup_system(spawn_example)
+        .add_system(bob)
+        .run();
+}
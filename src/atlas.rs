@@ -0,0 +1,228 @@
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::utils::HashMap;
+use bevy::utils::HashSet;
+
+use crate::diagnostics::ExactImageProblem;
+use crate::diagnostics::ExactImageWarning;
+use crate::layers::ExactImageLayers;
+use crate::layers::ImageLayer;
+use crate::layers::LayerImage;
+use crate::source::ExactSourceImage;
+use crate::sprite::ExactSprite;
+use crate::ExactAtlasImage;
+
+/// Maps the names of the frames of a texture atlas to their indices
+#[derive(TypeUuid, Clone, Default, Debug)]
+#[uuid = "5b7d3d5e-6a0e-4f47-9c1b-2a53c4d0f8a1"]
+pub struct AtlasFrameNames {
+    pub names: HashMap<String, usize>,
+}
+
+impl AtlasFrameNames {
+    pub fn new<S: Into<String>>(names: impl IntoIterator<Item = (S, usize)>) -> Self {
+        Self {
+            names: names
+                .into_iter()
+                .map(|(name, index)| (name.into(), index))
+                .collect(),
+        }
+    }
+
+    /// The index of the frame with the given name
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+}
+
+/// Selects the frame of the entity's atlas image by name.
+///
+/// The name is resolved to the index of the entity's [`ExactAtlasImage`], or of the
+/// [`ImageSource::Atlas`](crate::source::ImageSource::Atlas) of its [`ExactSourceImage`] or [`ExactSprite`],
+/// or of one of its [`ExactImageLayers`] with [`NamedAtlasFrame::layer`].
+/// Names are resolved in the main world, before layout, when they change or their names asset loads.
+/// Until then, the index is left unchanged.
+#[derive(Component, Clone, Default, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default)]
+pub struct NamedAtlasFrame {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub names: Handle<AtlasFrameNames>,
+    pub name: String,
+    /// Index of the layer whose frame is selected, `None` for the image of the entity
+    pub layer: Option<usize>,
+}
+
+impl NamedAtlasFrame {
+    pub fn new(names: Handle<AtlasFrameNames>, name: impl Into<String>) -> Self {
+        Self {
+            names,
+            name: name.into(),
+            layer: None,
+        }
+    }
+
+    /// Select the frame of a layer of the entity's [`ExactImageLayers`]
+    pub fn layer(mut self, layer: usize) -> Self {
+        self.layer = Some(layer);
+        self
+    }
+}
+
+/// Sets the atlas index returned by `index_mut`, only marking the component as changed if it differs.
+///
+/// Returns false if the component has no atlas index.
+pub(crate) fn set_atlas_index<T: Component>(
+    component: &mut Mut<T>,
+    index_mut: impl FnOnce(&mut T) -> Option<&mut usize>,
+    index: usize,
+) -> bool {
+    let Some(current) = index_mut(component.bypass_change_detection()) else {
+        return false;
+    };
+    let changed = *current != index;
+    *current = index;
+    if changed {
+        component.set_changed();
+    }
+    true
+}
+
+/// Resolves the names of [`NamedAtlasFrame`]s to atlas indices.
+///
+/// Frames are only resolved when they or their entity's images are added, when the frame changes,
+/// or when their names asset loads or changes.
+/// A missing name, or an entity without an atlas image for its frame, is warned about once,
+/// until the frame resolves or is removed.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn resolve_named_atlas_frames(
+    mut names_events: EventReader<AssetEvent<AtlasFrameNames>>,
+    frame_names: Res<Assets<AtlasFrameNames>>,
+    mut warned: Local<HashMap<Entity, ExactImageProblem>>,
+    mut warnings: EventWriter<ExactImageWarning>,
+    mut images: ParamSet<(
+        Query<
+            (
+                Entity,
+                &NamedAtlasFrame,
+                Option<&mut ExactAtlasImage>,
+                Option<&mut ExactSourceImage>,
+                Option<&mut ExactSprite>,
+                Option<&mut ExactImageLayers>,
+            ),
+            Or<(
+                Changed<NamedAtlasFrame>,
+                Added<ExactAtlasImage>,
+                Added<ExactSourceImage>,
+                Added<ExactSprite>,
+                Added<ExactImageLayers>,
+            )>,
+        >,
+        Query<(
+            Entity,
+            &NamedAtlasFrame,
+            Option<&mut ExactAtlasImage>,
+            Option<&mut ExactSourceImage>,
+            Option<&mut ExactSprite>,
+            Option<&mut ExactImageLayers>,
+        )>,
+    )>,
+    removed_frames: RemovedComponents<NamedAtlasFrame>,
+) {
+    for id in removed_frames.iter() {
+        warned.remove(&id);
+    }
+    let loaded: HashSet<Handle<AtlasFrameNames>> = names_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                Some(handle.clone_weak())
+            }
+            AssetEvent::Removed { .. } => None,
+        })
+        .collect();
+    let mut resolve = |id: Entity,
+                       frame: &NamedAtlasFrame,
+                       atlas_image: Option<Mut<ExactAtlasImage>>,
+                       source_image: Option<Mut<ExactSourceImage>>,
+                       sprite: Option<Mut<ExactSprite>>,
+                       layers: Option<Mut<ExactImageLayers>>| {
+        let Some(names) = frame_names.get(&frame.names) else {
+            return;
+        };
+        let problem = match names.get(&frame.name) {
+            Some(index) => {
+                let resolved = match (frame.layer, atlas_image, source_image, sprite, layers) {
+                    (Some(layer), .., Some(mut layers)) => set_atlas_index(
+                        &mut layers,
+                        |layers| match layers.layers.get_mut(layer) {
+                            Some(ImageLayer {
+                                image: LayerImage::Atlas { index, .. },
+                                ..
+                            }) => Some(index),
+                            _ => None,
+                        },
+                        index,
+                    ),
+                    (None, Some(mut atlas_image), ..) => {
+                        set_atlas_index(&mut atlas_image, |image| Some(&mut image.index), index)
+                    }
+                    (None, None, Some(mut source_image), ..) => set_atlas_index(
+                        &mut source_image,
+                        |image| image.source.atlas_index_mut(),
+                        index,
+                    ),
+                    (None, None, None, Some(mut sprite), _) => set_atlas_index(
+                        &mut sprite,
+                        |sprite| sprite.source.atlas_index_mut(),
+                        index,
+                    ),
+                    _ => false,
+                };
+                if resolved {
+                    warned.remove(&id);
+                    return;
+                }
+                ExactImageProblem::NoAtlasForFrameName {
+                    name: frame.name.clone(),
+                    layer: frame.layer,
+                }
+            }
+            None => ExactImageProblem::MissingFrameName(frame.name.clone()),
+        };
+        if warned.get(&id) != Some(&problem) {
+            match &problem {
+                ExactImageProblem::NoAtlasForFrameName {
+                    layer: Some(layer), ..
+                } => warn!(
+                    "{:?} has no atlas layer {} to select the frame \"{}\" of",
+                    id, layer, frame.name
+                ),
+                ExactImageProblem::NoAtlasForFrameName { layer: None, .. } => warn!(
+                    "{:?} has no atlas image to select the frame \"{}\" of",
+                    id, frame.name
+                ),
+                _ => warn!(
+                    "{:?} has no atlas frame named \"{}\", keeping its frame index",
+                    id, frame.name
+                ),
+            }
+            warned.insert(id, problem.clone());
+            warnings.send(ExactImageWarning {
+                entity: id,
+                problem,
+            });
+        }
+    };
+    for (id, frame, atlas_image, source_image, sprite, layers) in images.p0().iter_mut() {
+        resolve(id, frame, atlas_image, source_image, sprite, layers);
+    }
+    if loaded.is_empty() {
+        return;
+    }
+    for (id, frame, atlas_image, source_image, sprite, layers) in images.p1().iter_mut() {
+        if loaded.contains(&frame.names) {
+            resolve(id, frame, atlas_image, source_image, sprite, layers);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+
+use crate::import::ImportedAtlas;
+use crate::placeholder::LoadingPlaceholder;
+use crate::ExactAtlasImage;
+use crate::ExactAtlasImageBundle;
+use crate::ExactImage;
+use crate::ExactImageBundle;
+use crate::ExactSize;
+use crate::ImageAlignment;
+use crate::ImageBlendMode;
+
+impl ExactImage {
+    pub fn new(texture: Handle<Image>) -> Self {
+        Self {
+            texture,
+            ..Default::default()
+        }
+    }
+
+    pub fn size(mut self, size: ExactSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn align(mut self, alignment: ImageAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Rotate the image by the given angle in radians
+    pub fn rotate(mut self, rotation: f32) -> Self {
+        self.rotation = Some(rotation);
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn blend_mode(mut self, blend_mode: ImageBlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    pub fn loading_placeholder(mut self, placeholder: LoadingPlaceholder) -> Self {
+        self.loading_placeholder = Some(placeholder);
+        self
+    }
+
+    pub fn placeholder_size(mut self, size: Vec2) -> Self {
+        self.placeholder_size = Some(size);
+        self
+    }
+}
+
+impl ExactAtlasImage {
+    pub fn new(atlas: Handle<TextureAtlas>, index: usize) -> Self {
+        Self {
+            atlas,
+            index,
+            ..Default::default()
+        }
+    }
+
+    pub fn size(mut self, size: ExactSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn align(mut self, alignment: ImageAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Rotate the image by the given angle in radians
+    pub fn rotate(mut self, rotation: f32) -> Self {
+        self.rotation = Some(rotation);
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn blend_mode(mut self, blend_mode: ImageBlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    pub fn loading_placeholder(mut self, placeholder: LoadingPlaceholder) -> Self {
+        self.loading_placeholder = Some(placeholder);
+        self
+    }
+
+    pub fn placeholder_size(mut self, size: Vec2) -> Self {
+        self.placeholder_size = Some(size);
+        self
+    }
+
+    /// Size and align trimmed frames using the layout of an imported atlas
+    pub fn frames(mut self, frames: Handle<ImportedAtlas>) -> Self {
+        self.frames = Some(frames);
+        self
+    }
+}
+
+impl From<ExactImage> for ExactImageBundle {
+    fn from(image: ExactImage) -> Self {
+        Self {
+            image,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ExactAtlasImage> for ExactAtlasImageBundle {
+    fn from(image: ExactAtlasImage) -> Self {
+        Self {
+            image,
+            ..Default::default()
+        }
+    }
+}
+
+impl ExactImageBundle {
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl ExactAtlasImageBundle {
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// Spawns exact images from [`Commands`] or a [`ChildBuilder`].
+///
+/// The returned [`EntityCommands`] can insert a [`Style`] or any other components,
+/// which replace those of the bundle.
+pub trait SpawnExactImage<'w, 's> {
+    fn spawn_exact_image(&mut self, image: ExactImage) -> EntityCommands<'w, 's, '_>;
+
+    fn spawn_exact_atlas_image(&mut self, image: ExactAtlasImage) -> EntityCommands<'w, 's, '_>;
+}
+
+impl<'w, 's> SpawnExactImage<'w, 's> for Commands<'w, 's> {
+    fn spawn_exact_image(&mut self, image: ExactImage) -> EntityCommands<'w, 's, '_> {
+        self.spawn(ExactImageBundle::from(image))
+    }
+
+    fn spawn_exact_atlas_image(&mut self, image: ExactAtlasImage) -> EntityCommands<'w, 's, '_> {
+        self.spawn(ExactAtlasImageBundle::from(image))
+    }
+}
+
+impl<'w, 's> SpawnExactImage<'w, 's> for ChildBuilder<'w, 's, '_> {
+    fn spawn_exact_image(&mut self, image: ExactImage) -> EntityCommands<'w, 's, '_> {
+        self.spawn(ExactImageBundle::from(image))
+    }
+
+    fn spawn_exact_atlas_image(&mut self, image: ExactAtlasImage) -> EntityCommands<'w, 's, '_> {
+        self.spawn(ExactAtlasImageBundle::from(image))
+    }
+}
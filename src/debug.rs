@@ -0,0 +1,384 @@
+use bevy::prelude::*;
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy::render::Extract;
+use bevy::render::RenderApp;
+use bevy::render::RenderStage;
+use bevy::transform::TransformSystem;
+use bevy::ui::ExtractedUiNode;
+use bevy::ui::ExtractedUiNodes;
+use bevy::ui::UiStack;
+use bevy::utils::HashMap;
+
+use crate::descriptor::ExactImageDescriptor;
+use crate::diagnostics::InvalidImageFallback;
+use crate::import::ImportedAtlas;
+use crate::layers::extract_exact_image_layers;
+use crate::source::ExactSourceImage;
+use crate::source::ImageGeometry;
+use crate::source::SourceRect;
+use crate::ExactSize;
+
+/// Draws the node bounds, image quad, alignment anchor and rotation pivot of exact images,
+/// with a label showing the `ExactSize` of each image and its size in pixels.
+///
+/// The overlay can be toggled for every image with [`ExactImageDebugSettings::enabled`],
+/// or for a single image with an [`ExactImageDebug`] component.
+pub struct ExactImageDebugPlugin;
+
+impl Plugin for ExactImageDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ExactImageDebug>()
+            .init_resource::<ExactImageDebugSettings>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_exact_image_debug_labels.after(TransformSystem::TransformPropagate),
+            );
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app.add_system_to_stage(
+            RenderStage::Extract,
+            extract_exact_image_debug.after(extract_exact_image_layers),
+        );
+    }
+}
+
+#[derive(Resource, Clone)]
+pub struct ExactImageDebugSettings {
+    /// Draw the overlay for every exact image without an [`ExactImageDebug`] component
+    pub enabled: bool,
+    pub node_color: Color,
+    pub image_color: Color,
+    pub anchor_color: Color,
+    pub pivot_color: Color,
+    /// Width of the outlines in logical pixels
+    pub line_width: f32,
+    /// Font of the labels, which aren't shown without one
+    pub font: Option<Handle<Font>>,
+    pub font_size: f32,
+    pub label_color: Color,
+}
+
+impl Default for ExactImageDebugSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            node_color: Color::YELLOW,
+            image_color: Color::CYAN,
+            anchor_color: Color::ORANGE_RED,
+            pivot_color: Color::LIME_GREEN,
+            line_width: 1.,
+            font: None,
+            font_size: 12.,
+            label_color: Color::WHITE,
+        }
+    }
+}
+
+/// Shows or hides the overlay of a single exact image, overriding [`ExactImageDebugSettings::enabled`]
+#[derive(Component, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default)]
+pub struct ExactImageDebug {
+    pub enabled: bool,
+}
+
+impl Default for ExactImageDebug {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Marks the text entities spawned to label debugged images
+#[derive(Component)]
+pub struct ExactImageDebugLabel;
+
+/// Geometry of an image whose source size is known
+#[allow(clippy::too_many_arguments)]
+fn debug_geometry(
+    image: &ExactSourceImage,
+    node: &Node,
+    style: &Style,
+    transform: &GlobalTransform,
+    textures: &Assets<Image>,
+    atlases: &Assets<TextureAtlas>,
+    imported_atlases: &Assets<ImportedAtlas>,
+    descriptors: &Assets<ExactImageDescriptor>,
+    fallback: InvalidImageFallback,
+    scale_factor: f32,
+) -> Option<ImageGeometry> {
+    let source_size = match image.source.rect(textures, atlases, descriptors, fallback) {
+        SourceRect::Ready(rect) => image
+            .imported_frame(imported_atlases)
+            .map_or(rect.size(), |frame| frame.source_size),
+        SourceRect::Loading => image.placeholder_size?,
+        SourceRect::Invalid => return None,
+    };
+    let frame = image.imported_frame(imported_atlases);
+    Some(image.geometry(frame, node, style, transform, source_size, scale_factor))
+}
+
+fn size_label(size: ExactSize) -> String {
+    match size {
+        ExactSize::AttemptPreserveAspectRatio => "AttemptPreserveAspectRatio".into(),
+        ExactSize::ForcePreserveAspectRatio => "ForcePreserveAspectRatio".into(),
+        ExactSize::FillNode => "FillNode".into(),
+        ExactSize::Texture => "Texture".into(),
+        ExactSize::Scaled(scale) => format!("Scaled({}, {})", scale.x, scale.y),
+        ExactSize::Exactly(size) => format!("Exactly({}, {})", size.x, size.y),
+    }
+}
+
+/// A solid quad of the given size, centered on the transform
+fn debug_quad(
+    stack_index: usize,
+    transform: Mat4,
+    size: Vec2,
+    color: Color,
+    scale_factor: f32,
+) -> ExtractedUiNode {
+    ExtractedUiNode {
+        stack_index,
+        transform,
+        background_color: color,
+        rect: Rect {
+            min: Vec2::ZERO,
+            max: size,
+        },
+        image: DEFAULT_IMAGE_HANDLE.typed().clone_weak(),
+        atlas_size: None,
+        clip: None,
+        scale_factor,
+    }
+}
+
+/// The edges of a box of the given size, centered on the transform
+fn outline_quads(
+    stack_index: usize,
+    transform: Mat4,
+    size: Vec2,
+    width: f32,
+    color: Color,
+    scale_factor: f32,
+) -> impl Iterator<Item = ExtractedUiNode> {
+    let horizontal = Vec2::new(size.x + width, width);
+    let vertical = Vec2::new(width, size.y + width);
+    [
+        (Vec2::new(0., -0.5 * size.y), horizontal),
+        (Vec2::new(0., 0.5 * size.y), horizontal),
+        (Vec2::new(-0.5 * size.x, 0.), vertical),
+        (Vec2::new(0.5 * size.x, 0.), vertical),
+    ]
+    .into_iter()
+    .map(move |(center, edge)| {
+        debug_quad(
+            stack_index,
+            transform * Mat4::from_translation(center.extend(0.)),
+            edge,
+            color,
+            scale_factor,
+        )
+    })
+}
+
+/// A cross marking a point, turned by the given angle
+fn marker_quads(
+    stack_index: usize,
+    point: Vec2,
+    angle: f32,
+    width: f32,
+    color: Color,
+    scale_factor: f32,
+) -> impl Iterator<Item = ExtractedUiNode> {
+    let transform = Mat4::from_translation(point.extend(0.)) * Mat4::from_rotation_z(angle);
+    let arm = 8. * width;
+    [Vec2::new(arm, width), Vec2::new(width, arm)]
+        .into_iter()
+        .map(move |size| debug_quad(stack_index, transform, size, color, scale_factor))
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn extract_exact_image_debug(
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    settings: Extract<Res<ExactImageDebugSettings>>,
+    images: Extract<Res<Assets<Image>>>,
+    texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
+    imported_atlases: Extract<Res<Assets<ImportedAtlas>>>,
+    descriptors: Extract<Res<Assets<ExactImageDescriptor>>>,
+    fallback: Extract<Res<InvalidImageFallback>>,
+    ui_scale: Extract<Res<UiScale>>,
+    ui_stack: Extract<Res<UiStack>>,
+    uinode_query: Extract<
+        Query<(
+            &Node,
+            &Style,
+            &ExactSourceImage,
+            &GlobalTransform,
+            &ComputedVisibility,
+            Option<&ExactImageDebug>,
+        )>,
+    >,
+) {
+    let scale_factor = ui_scale.scale as f32;
+    let width = settings.line_width * scale_factor;
+    for (stack_index, entity) in ui_stack.uinodes.iter().enumerate() {
+        let Ok((node, style, image, transform, visibility, debug)) = uinode_query.get(*entity)
+        else {
+            continue;
+        };
+        if !visibility.is_visible() || !debug.map_or(settings.enabled, |debug| debug.enabled) {
+            continue;
+        }
+        extracted_uinodes.uinodes.extend(outline_quads(
+            stack_index,
+            transform.compute_matrix(),
+            node.size(),
+            width,
+            settings.node_color,
+            scale_factor,
+        ));
+        let Some(geometry) = debug_geometry(
+            image,
+            node,
+            style,
+            transform,
+            &images,
+            &texture_atlases,
+            &imported_atlases,
+            &descriptors,
+            **fallback,
+            scale_factor,
+        ) else {
+            continue;
+        };
+        extracted_uinodes.uinodes.extend(outline_quads(
+            stack_index,
+            geometry.transform,
+            geometry.size,
+            width,
+            settings.image_color,
+            scale_factor,
+        ));
+        extracted_uinodes.uinodes.extend(marker_quads(
+            stack_index,
+            geometry.anchor,
+            0.,
+            width,
+            settings.anchor_color,
+            scale_factor,
+        ));
+        // the pivot is marked with a diagonal cross, so it can be told apart when it's on the anchor
+        extracted_uinodes.uinodes.extend(marker_quads(
+            stack_index,
+            geometry.pivot,
+            std::f32::consts::FRAC_PI_4,
+            width,
+            settings.pivot_color,
+            scale_factor,
+        ));
+    }
+}
+
+/// Spawns a label at the top left corner of each debugged image, and despawns it once the image isn't debugged
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn update_exact_image_debug_labels(
+    mut commands: Commands,
+    settings: Res<ExactImageDebugSettings>,
+    textures: Res<Assets<Image>>,
+    atlases: Res<Assets<TextureAtlas>>,
+    imported_atlases: Res<Assets<ImportedAtlas>>,
+    descriptors: Res<Assets<ExactImageDescriptor>>,
+    fallback: Res<InvalidImageFallback>,
+    ui_scale: Res<UiScale>,
+    mut labels: Local<HashMap<Entity, Entity>>,
+    images: Query<
+        (
+            Entity,
+            &Node,
+            &Style,
+            &ExactSourceImage,
+            &GlobalTransform,
+            &ComputedVisibility,
+            Option<&ExactImageDebug>,
+        ),
+        Without<ExactImageDebugLabel>,
+    >,
+    mut label_query: Query<(&mut Text, &mut Style), With<ExactImageDebugLabel>>,
+) {
+    let scale_factor = ui_scale.scale as f32;
+    let mut unused = std::mem::take(&mut *labels);
+    if let Some(font) = &settings.font {
+        for (id, node, style, image, transform, visibility, debug) in images.iter() {
+            if !visibility.is_visible() || !debug.map_or(settings.enabled, |debug| debug.enabled) {
+                continue;
+            }
+            let value = match debug_geometry(
+                image,
+                node,
+                style,
+                transform,
+                &textures,
+                &atlases,
+                &imported_atlases,
+                &descriptors,
+                *fallback,
+                scale_factor,
+            ) {
+                Some(geometry) => format!(
+                    "{} {}x{}",
+                    size_label(image.size),
+                    geometry.size.x.round(),
+                    geometry.size.y.round()
+                ),
+                None => size_label(image.size),
+            };
+            let corner = transform.translation().truncate() - 0.5 * node.size();
+            let position = UiRect {
+                left: Val::Px(corner.x / scale_factor),
+                top: Val::Px(corner.y / scale_factor),
+                ..Default::default()
+            };
+            let label = unused.remove(&id);
+            match label.and_then(|label| Some((label, label_query.get_mut(label).ok()?))) {
+                Some((label, (mut text, mut label_style))) => {
+                    if text.sections[0].value != value {
+                        text.sections[0].value = value;
+                    }
+                    if label_style.position != position {
+                        label_style.position = position;
+                    }
+                    labels.insert(id, label);
+                }
+                None => {
+                    let text_style = TextStyle {
+                        font: font.clone(),
+                        font_size: settings.font_size,
+                        color: settings.label_color,
+                    };
+                    let label = commands
+                        .spawn((
+                            TextBundle {
+                                z_index: ZIndex::Global(i32::MAX),
+                                ..TextBundle::from_section(value, text_style).with_style(Style {
+                                    position_type: PositionType::Absolute,
+                                    position,
+                                    ..Default::default()
+                                })
+                            },
+                            ExactImageDebugLabel,
+                        ))
+                        .id();
+                    labels.insert(id, label);
+                }
+            }
+        }
+    }
+    for label in unused.into_values() {
+        if let Some(entity) = commands.get_entity(label) {
+            entity.despawn_recursive();
+        }
+    }
+}
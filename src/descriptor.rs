@@ -0,0 +1,379 @@
+use bevy::asset::AssetLoader;
+use bevy::asset::AssetPath;
+use bevy::asset::LoadContext;
+use bevy::asset::LoadedAsset;
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use bevy::utils::HashMap;
+use bevy::utils::HashSet;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::source::ExactSourceImage;
+use crate::source::ImageSource;
+use crate::ExactAtlasImage;
+use crate::ExactImage;
+use crate::ExactSize;
+use crate::ImageAlignment;
+
+/// Borders of a nine-slice image in texture pixels.
+///
+/// The corners keep their size, the edges are stretched along their length and
+/// the center is stretched to fill the rest of the image.
+/// Outlines and shadows aren't drawn around nine-slice images.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NineSlice {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+/// Splits `[start, end]` at the given borders, shrinking the borders if they don't fit
+fn slice_edges(start: f32, end: f32, first: f32, last: f32) -> [f32; 4] {
+    let shrink = ((end - start) / (first + last)).min(1.);
+    [start, start + first * shrink, end - last * shrink, end]
+}
+
+impl NineSlice {
+    /// The region of the texture and of the image covered by each non-empty slice,
+    /// for an image of the given size drawn from `rect` and centered on the origin.
+    ///
+    /// The borders are multiplied by `scale_factor` within the image,
+    /// and shrink evenly if the image is too small to fit them.
+    pub fn slices(
+        self,
+        rect: Rect,
+        size: Vec2,
+        scale_factor: f32,
+    ) -> impl Iterator<Item = (Rect, Rect)> {
+        let source_x = slice_edges(rect.min.x, rect.max.x, self.left, self.right);
+        let source_y = slice_edges(rect.min.y, rect.max.y, self.top, self.bottom);
+        let target_x = slice_edges(
+            -0.5 * size.x,
+            0.5 * size.x,
+            self.left * scale_factor,
+            self.right * scale_factor,
+        );
+        let target_y = slice_edges(
+            -0.5 * size.y,
+            0.5 * size.y,
+            self.top * scale_factor,
+            self.bottom * scale_factor,
+        );
+        (0..3)
+            .flat_map(|row| (0..3).map(move |column| (row, column)))
+            .map(move |(row, column)| {
+                (
+                    Rect::new(
+                        source_x[column],
+                        source_y[row],
+                        source_x[column + 1],
+                        source_y[row + 1],
+                    ),
+                    Rect::new(
+                        target_x[column],
+                        target_y[row],
+                        target_x[column + 1],
+                        target_y[row + 1],
+                    ),
+                )
+            })
+            .filter(|(source, target)| !source.is_empty() && !target.is_empty())
+    }
+}
+
+/// A sequence of regions of the descriptor's texture, drawn one after another
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+pub struct ImageClip {
+    /// Regions of the texture in pixels
+    pub frames: Vec<Rect>,
+    pub frames_per_second: f32,
+    /// Stop at the last frame if false
+    #[serde(default)]
+    pub looping: bool,
+}
+
+impl ImageClip {
+    /// Index of the frame shown after the clip has played for `elapsed` seconds
+    pub fn frame_index(&self, elapsed: f32) -> Option<usize> {
+        let count = self.frames.len();
+        if count == 0 {
+            return None;
+        }
+        let index = (elapsed * self.frames_per_second).max(0.) as usize;
+        Some(if self.looping {
+            index % count
+        } else {
+            index.min(count - 1)
+        })
+    }
+
+    /// The frame shown after the clip has played for `elapsed` seconds
+    pub fn frame(&self, elapsed: f32) -> Option<Rect> {
+        self.frame_index(elapsed).map(|index| self.frames[index])
+    }
+}
+
+/// How an image is presented, loaded from a `*.exactimage.ron` file.
+///
+/// Draw it with an [`ExactSourceImage`] whose source is an [`ImageSource::Descriptor`],
+/// or add a `Handle<ExactImageDescriptor>` to a ui node,
+/// and add a [`DescriptorAnimation`] to play one of its clips.
+#[derive(TypeUuid, Clone)]
+#[uuid = "e2dc2c7c-b3c2-48ab-86f8-02868dea2777"]
+pub struct ExactImageDescriptor {
+    pub texture: Handle<Image>,
+    /// Region of the texture to draw in pixels, the whole texture if `None`
+    pub rect: Option<Rect>,
+    pub size: ExactSize,
+    pub alignment: ImageAlignment,
+    /// rotation of the image in radians
+    pub rotation: Option<f32>,
+    pub color: Color,
+    pub nine_slice: Option<NineSlice>,
+    pub clips: HashMap<String, ImageClip>,
+}
+
+impl ExactImageDescriptor {
+    /// Region of the texture drawn for the given frame of a clip, or without a clip
+    pub fn source_rect(&self, texture_size: Vec2, clip: Option<&str>, frame: usize) -> Rect {
+        clip.and_then(|clip| self.clips.get(clip))
+            .and_then(|clip| clip.frames.get(frame).copied())
+            .or(self.rect)
+            .unwrap_or(Rect {
+                min: Vec2::ZERO,
+                max: texture_size,
+            })
+    }
+}
+
+fn default_color() -> Color {
+    Color::WHITE
+}
+
+/// The contents of a `*.exactimage.ron` file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DescriptorFile {
+    /// Path of the texture, relative to the assets folder
+    texture: String,
+    #[serde(default)]
+    rect: Option<Rect>,
+    #[serde(default)]
+    size: ExactSize,
+    #[serde(default)]
+    alignment: ImageAlignment,
+    #[serde(default)]
+    rotation: Option<f32>,
+    #[serde(default = "default_color")]
+    color: Color,
+    #[serde(default)]
+    nine_slice: Option<NineSlice>,
+    #[serde(default)]
+    clips: HashMap<String, ImageClip>,
+}
+
+#[derive(Default)]
+pub struct ExactImageDescriptorLoader;
+
+impl AssetLoader for ExactImageDescriptorLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let file: DescriptorFile = ron::de::from_bytes(bytes)?;
+            let texture_path = AssetPath::from(file.texture);
+            let descriptor = ExactImageDescriptor {
+                texture: load_context.get_handle(texture_path.clone()),
+                rect: file.rect,
+                size: file.size,
+                alignment: file.alignment,
+                rotation: file.rotation,
+                color: file.color,
+                nine_slice: file.nine_slice,
+                clips: file.clips,
+            };
+            load_context
+                .set_default_asset(LoadedAsset::new(descriptor).with_dependency(texture_path));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["exactimage.ron"]
+    }
+}
+
+/// Plays a clip of the node's [`ExactImageDescriptor`]
+#[derive(Component, Clone, Default, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default)]
+pub struct DescriptorAnimation {
+    /// Name of the clip
+    pub clip: String,
+    /// Seconds since the clip started, reset to zero to restart it
+    pub elapsed: f32,
+}
+
+impl DescriptorAnimation {
+    pub fn new(clip: impl Into<String>) -> Self {
+        Self {
+            clip: clip.into(),
+            elapsed: 0.,
+        }
+    }
+}
+
+pub fn advance_descriptor_animations(
+    time: Res<Time>,
+    mut animations: Query<&mut DescriptorAnimation>,
+) {
+    for mut animation in animations.iter_mut() {
+        animation.elapsed += time.delta_seconds();
+    }
+}
+
+/// Draws ui nodes with a `Handle<ExactImageDescriptor>` through the [`ExactSourceImage`] of the node,
+/// inserting one if needed. Nodes with an [`ExactImage`] or [`ExactAtlasImage`] are drawn from those instead.
+#[allow(clippy::type_complexity)]
+pub fn sync_descriptor_sources(
+    mut commands: Commands,
+    removed: RemovedComponents<Handle<ExactImageDescriptor>>,
+    mut changed: Query<
+        (
+            Entity,
+            &Handle<ExactImageDescriptor>,
+            Option<&mut ExactSourceImage>,
+        ),
+        (
+            Changed<Handle<ExactImageDescriptor>>,
+            Without<ExactImage>,
+            Without<ExactAtlasImage>,
+        ),
+    >,
+    images: Query<&ExactSourceImage, Without<Handle<ExactImageDescriptor>>>,
+) {
+    for (id, handle, source_image) in changed.iter_mut() {
+        match source_image {
+            Some(mut source_image) => {
+                // keep the clip and frame of the descriptor already drawn
+                if !matches!(
+                    &source_image.source,
+                    ImageSource::Descriptor { descriptor, .. } if descriptor == handle
+                ) {
+                    source_image.source = handle.clone().into();
+                }
+            }
+            None => {
+                commands.entity(id).insert(ExactSourceImage {
+                    source: handle.clone().into(),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+    for id in removed.iter() {
+        if matches!(
+            images.get(id),
+            Ok(ExactSourceImage {
+                source: ImageSource::Descriptor { .. },
+                ..
+            })
+        ) {
+            commands.entity(id).remove::<ExactSourceImage>();
+        }
+    }
+}
+
+/// Copies the settings of a loaded descriptor into an image drawn from it,
+/// along with the frame of the clip its animation is playing
+fn apply_descriptor(
+    mut image: Mut<ExactSourceImage>,
+    animation: Option<&DescriptorAnimation>,
+    descriptors: &Assets<ExactImageDescriptor>,
+) {
+    let ImageSource::Descriptor {
+        descriptor: handle,
+        clip,
+        frame,
+    } = &image.source
+    else {
+        return;
+    };
+    let Some(descriptor) = descriptors.get(handle) else {
+        return;
+    };
+    let playing = animation
+        .and_then(|animation| {
+            let index = descriptor
+                .clips
+                .get(&animation.clip)?
+                .frame_index(animation.elapsed)?;
+            Some((Some(animation.clip.as_str()), index))
+        })
+        .unwrap_or((None, 0));
+    if (clip.as_deref(), *frame) != playing {
+        image.source = ImageSource::Descriptor {
+            descriptor: handle.clone(),
+            clip: playing.0.map(String::from),
+            frame: playing.1,
+        };
+    }
+    // only write the settings that differ, so the image isn't changed every frame
+    if image.size != descriptor.size {
+        image.size = descriptor.size;
+    }
+    if image.alignment != descriptor.alignment {
+        image.alignment = descriptor.alignment;
+    }
+    if image.rotation != descriptor.rotation {
+        image.rotation = descriptor.rotation;
+    }
+    if image.color != descriptor.color {
+        image.color = descriptor.color;
+    }
+}
+
+/// Applies the settings and clip frames of [`ExactImageDescriptor`]s to the images drawn from them.
+///
+/// Images are only updated when their descriptor loads or changes, or when their source or animation changes.
+#[allow(clippy::type_complexity)]
+pub fn apply_image_descriptors(
+    mut descriptor_events: EventReader<AssetEvent<ExactImageDescriptor>>,
+    descriptors: Res<Assets<ExactImageDescriptor>>,
+    mut images: ParamSet<(
+        Query<
+            (&mut ExactSourceImage, Option<&DescriptorAnimation>),
+            Or<(Changed<ExactSourceImage>, Changed<DescriptorAnimation>)>,
+        >,
+        Query<(&mut ExactSourceImage, Option<&DescriptorAnimation>)>,
+    )>,
+) {
+    let loaded: HashSet<Handle<ExactImageDescriptor>> = descriptor_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                Some(handle.clone_weak())
+            }
+            AssetEvent::Removed { .. } => None,
+        })
+        .collect();
+    for (image, animation) in images.p0().iter_mut() {
+        apply_descriptor(image, animation, &descriptors);
+    }
+    if loaded.is_empty() {
+        return;
+    }
+    for (image, animation) in images.p1().iter_mut() {
+        if matches!(
+            &image.source,
+            ImageSource::Descriptor { descriptor, .. } if loaded.contains(descriptor)
+        ) {
+            apply_descriptor(image, animation, &descriptors);
+        }
+    }
+}
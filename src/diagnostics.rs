@@ -0,0 +1,195 @@
+use bevy::prelude::*;
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy::ui::ExtractedUiNode;
+use bevy::utils::HashSet;
+
+use crate::layers::ExactImageLayers;
+use crate::layers::ImageLayer;
+use crate::layers::LayerImage;
+use crate::source::ExactSourceImage;
+use crate::source::ImageSource;
+
+/// What to draw in place of an exact image with an invalid atlas index
+#[derive(Resource, Copy, Clone, Default, PartialEq, Eq, Debug, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource, Default)]
+pub enum InvalidImageFallback {
+    /// Draw nothing
+    #[default]
+    Skip,
+    /// Fill the node with magenta
+    Placeholder,
+    /// Draw the last frame of the atlas
+    Clamp,
+}
+
+impl InvalidImageFallback {
+    /// The rect of the atlas frame to draw for the given index
+    pub(crate) fn atlas_rect(self, texture_atlas: &TextureAtlas, index: usize) -> Option<Rect> {
+        match texture_atlas.textures.get(index) {
+            Some(rect) => Some(*rect),
+            None if self == InvalidImageFallback::Clamp => texture_atlas.textures.last().copied(),
+            None => None,
+        }
+    }
+}
+
+/// Magenta quad covering the node, drawn by [`InvalidImageFallback::Placeholder`]
+pub(crate) fn placeholder_node(
+    stack_index: usize,
+    node: &Node,
+    transform: &GlobalTransform,
+    clip: Option<&CalculatedClip>,
+    scale_factor: f32,
+) -> ExtractedUiNode {
+    ExtractedUiNode {
+        stack_index,
+        transform: transform.compute_matrix(),
+        background_color: Color::FUCHSIA,
+        rect: Rect {
+            min: Vec2::ZERO,
+            max: node.size(),
+        },
+        image: DEFAULT_IMAGE_HANDLE.typed().clone_weak(),
+        atlas_size: None,
+        clip: clip.map(|clip| clip.clip),
+        scale_factor,
+    }
+}
+
+/// Why an exact image can't be drawn as specified
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExactImageProblem {
+    /// The atlas has fewer frames than the index
+    AtlasIndexOutOfRange {
+        /// Index of the layer of [`ExactImageLayers`], `None` for an [`ExactSourceImage`]
+        layer: Option<usize>,
+        index: usize,
+        frames: usize,
+    },
+    /// The frame names asset of a [`crate::atlas::NamedAtlasFrame`] doesn't contain the name
+    MissingFrameName(String),
+    /// The entity of a [`crate::atlas::NamedAtlasFrame`] has no atlas image, or no atlas layer, to select the frame of
+    NoAtlasForFrameName {
+        name: String,
+        /// The layer of [`ExactImageLayers`] selected by the frame
+        layer: Option<usize>,
+    },
+}
+
+/// Sent once when an entity's exact image becomes invalid
+#[derive(Clone, Debug)]
+pub struct ExactImageWarning {
+    pub entity: Entity,
+    pub problem: ExactImageProblem,
+}
+
+/// Checks the atlas indices of exact images once their atlases have loaded.
+///
+/// Images are only checked when they change or their atlas loads, modified atlases mark their images as changed.
+/// Each problem is logged and sent as an [`ExactImageWarning`] once, until it is fixed
+/// or the image is removed.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn validate_exact_atlas_images(
+    mut atlas_events: EventReader<AssetEvent<TextureAtlas>>,
+    atlases: Res<Assets<TextureAtlas>>,
+    mut warned: Local<HashSet<(Entity, Option<usize>)>>,
+    mut warnings: EventWriter<ExactImageWarning>,
+    changed_source_images: Query<(Entity, &ExactSourceImage), Changed<ExactSourceImage>>,
+    changed_layers: Query<(Entity, &ExactImageLayers), Changed<ExactImageLayers>>,
+    source_images: Query<(Entity, &ExactSourceImage)>,
+    layers: Query<(Entity, &ExactImageLayers)>,
+    removed_source_images: RemovedComponents<ExactSourceImage>,
+    removed_layers: RemovedComponents<ExactImageLayers>,
+) {
+    for entity in removed_source_images.iter() {
+        warned.remove(&(entity, None));
+    }
+    for entity in removed_layers.iter() {
+        warned.retain(|(warned_entity, layer)| *warned_entity != entity || layer.is_none());
+    }
+    // images and layers that no longer draw an atlas frame can't have an invalid index
+    for (entity, source_image) in changed_source_images.iter() {
+        if !matches!(source_image.source, ImageSource::Atlas { .. }) {
+            warned.remove(&(entity, None));
+        }
+    }
+    for (entity, layers) in changed_layers.iter() {
+        warned.retain(|(warned_entity, layer)| {
+            let Some(layer) = layer.filter(|_| *warned_entity == entity) else {
+                return true;
+            };
+            matches!(
+                layers.layers.get(layer),
+                Some(ImageLayer {
+                    image: LayerImage::Atlas { .. },
+                    ..
+                })
+            )
+        });
+    }
+
+    let created: HashSet<Handle<TextureAtlas>> = atlas_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } => Some(handle.clone_weak()),
+            _ => None,
+        })
+        .collect();
+    let mut check = |entity: Entity, layer: Option<usize>, atlas: &Handle<TextureAtlas>, index| {
+        let Some(texture_atlas) = atlases.get(atlas) else {
+            return;
+        };
+        let frames = texture_atlas.textures.len();
+        if index < frames {
+            warned.remove(&(entity, layer));
+        } else if warned.insert((entity, layer)) {
+            warn!("{entity:?} has atlas index {index} but its atlas only has {frames} frames");
+            warnings.send(ExactImageWarning {
+                entity,
+                problem: ExactImageProblem::AtlasIndexOutOfRange {
+                    layer,
+                    index,
+                    frames,
+                },
+            });
+        }
+    };
+    let mut check_source_image = |entity, source_image: &ExactSourceImage| {
+        if let ImageSource::Atlas { atlas, index } = &source_image.source {
+            check(entity, None, atlas, *index);
+        }
+    };
+
+    for (entity, source_image) in changed_source_images.iter() {
+        check_source_image(entity, source_image);
+    }
+    if !created.is_empty() {
+        for (entity, source_image) in source_images.iter() {
+            if matches!(&source_image.source, ImageSource::Atlas { atlas, .. } if created.contains(atlas))
+            {
+                check_source_image(entity, source_image);
+            }
+        }
+    }
+
+    let mut check_layers = |entity, layers: &ExactImageLayers| {
+        for (layer, image_layer) in layers.layers.iter().enumerate() {
+            if let LayerImage::Atlas { atlas, index } = &image_layer.image {
+                check(entity, Some(layer), atlas, *index);
+            }
+        }
+    };
+    for (entity, layers) in changed_layers.iter() {
+        check_layers(entity, layers);
+    }
+    if !created.is_empty() {
+        for (entity, layers) in layers.iter() {
+            if layers.layers.iter().any(
+                |layer| matches!(&layer.image, LayerImage::Atlas { atlas, .. } if created.contains(atlas)),
+            ) {
+                check_layers(entity, layers);
+            }
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use std::fmt;
+use std::path::Path;
+
+use bevy::asset::AssetLoader;
+use bevy::asset::AssetPath;
+use bevy::asset::LoadContext;
+use bevy::asset::LoadedAsset;
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use bevy::utils::HashMap;
+use serde::de::MapAccess;
+use serde::de::Visitor;
+use serde::Deserialize;
+use serde::Deserializer;
+
+use crate::atlas::set_atlas_index;
+use crate::atlas::AtlasFrameNames;
+use crate::source::ExactSourceImage;
+use crate::sprite::ExactSprite;
+use crate::ExactAtlasImage;
+
+/// How a frame of an imported atlas was laid out before it was packed
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ImportedFrame {
+    /// Size of the frame before its transparent borders were trimmed
+    pub source_size: Vec2,
+    /// Position of the trimmed frame within its untrimmed source, from the top left
+    pub trim_offset: Vec2,
+    /// Pivot point of the untrimmed frame, from (0, 0) at the top left to (1, 1) at the bottom right.
+    ///
+    /// The untrimmed frame is aligned within the node like any other image, and rotated around its pivot.
+    pub pivot: Option<Vec2>,
+    /// How long the frame is shown for in seconds
+    pub duration: Option<f32>,
+}
+
+impl ImportedFrame {
+    /// Offset from the center of the untrimmed source to the center of the trimmed frame
+    pub fn trimmed_center(&self, trimmed_size: Vec2) -> Vec2 {
+        self.trim_offset + 0.5 * (trimmed_size - self.source_size)
+    }
+}
+
+/// The order that the frames of an [`AtlasTag`] are played in
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Reflect, FromReflect)]
+#[reflect(Default)]
+pub enum TagDirection {
+    #[default]
+    Forward,
+    Reverse,
+    /// Forward then back again, without repeating the first and last frames
+    PingPong,
+    /// Back then forward again, without repeating the last and first frames
+    PingPongReverse,
+}
+
+/// A named range of frames, exported by Aseprite from its animation tags
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtlasTag {
+    /// Index of the first frame
+    pub from: usize,
+    /// Index of the last frame, inclusive
+    pub to: usize,
+    pub direction: TagDirection,
+}
+
+impl AtlasTag {
+    /// Indices of the tag's frames in the order they are played
+    pub fn frames(&self) -> Vec<usize> {
+        let forward = self.from..=self.to;
+        match self.direction {
+            TagDirection::Forward => forward.collect(),
+            TagDirection::Reverse => forward.rev().collect(),
+            TagDirection::PingPong => forward
+                .clone()
+                .chain((self.from + 1..self.to).rev())
+                .collect(),
+            TagDirection::PingPongReverse => forward.rev().chain(self.from + 1..self.to).collect(),
+        }
+    }
+}
+
+/// A texture atlas imported from a TexturePacker or Aseprite json file.
+///
+/// The atlas and frame names are also available as the labeled assets `#atlas` and `#names`,
+/// for use with [`ExactAtlasImage`] and [`crate::atlas::NamedAtlasFrame`].
+#[derive(TypeUuid, Clone, Debug)]
+#[uuid = "3f0d9a52-8f37-4c8e-b1a6-6c1e0f2d7a94"]
+pub struct ImportedAtlas {
+    pub atlas: Handle<TextureAtlas>,
+    pub names: Handle<AtlasFrameNames>,
+    /// Indexed like the frames of the atlas
+    pub frames: Vec<ImportedFrame>,
+    pub tags: HashMap<String, AtlasTag>,
+}
+
+impl ImportedAtlas {
+    /// Layout of the frame at the given index before it was packed
+    pub fn frame(&self, index: usize) -> Option<&ImportedFrame> {
+        self.frames.get(index)
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonRect {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+#[derive(Deserialize)]
+struct JsonSize {
+    w: f32,
+    h: f32,
+}
+
+#[derive(Deserialize)]
+struct JsonPoint {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonFrame {
+    #[serde(default)]
+    filename: Option<String>,
+    frame: JsonRect,
+    #[serde(default)]
+    rotated: bool,
+    #[serde(default)]
+    sprite_source_size: Option<JsonRect>,
+    #[serde(default)]
+    source_size: Option<JsonSize>,
+    #[serde(default)]
+    pivot: Option<JsonPoint>,
+    /// Milliseconds, Aseprite only
+    #[serde(default)]
+    duration: Option<f32>,
+}
+
+#[derive(Deserialize)]
+struct JsonTag {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonMeta {
+    image: String,
+    size: JsonSize,
+    #[serde(default)]
+    frame_tags: Vec<JsonTag>,
+}
+
+/// Both tools export frames either as an array or as a map keyed by name.
+/// The order of a map is kept, as it is the order of the frame indices.
+struct JsonFrames(Vec<(String, JsonFrame)>);
+
+impl<'de> Deserialize<'de> for JsonFrames {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FramesVisitor;
+
+        impl<'de> Visitor<'de> for FramesVisitor {
+            type Value = JsonFrames;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an array or map of frames")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut frames = Vec::new();
+                while let Some(frame) = seq.next_element::<JsonFrame>()? {
+                    let name = frame.filename.clone().unwrap_or_default();
+                    frames.push((name, frame));
+                }
+                Ok(JsonFrames(frames))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut frames = Vec::new();
+                while let Some(entry) = map.next_entry::<String, JsonFrame>()? {
+                    frames.push(entry);
+                }
+                Ok(JsonFrames(frames))
+            }
+        }
+
+        deserializer.deserialize_any(FramesVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonAtlas {
+    frames: JsonFrames,
+    meta: JsonMeta,
+}
+
+/// Creates the atlas and its labeled assets from the shared json format of TexturePacker and Aseprite
+fn load_json_atlas(bytes: &[u8], load_context: &mut LoadContext) -> Result<(), bevy::asset::Error> {
+    let json: JsonAtlas = serde_json::from_slice(bytes)?;
+    let texture_path = AssetPath::from(
+        load_context
+            .path()
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(&json.meta.image),
+    );
+    let texture = load_context.get_handle(texture_path.clone());
+    let mut atlas = TextureAtlas::new_empty(texture, Vec2::new(json.meta.size.w, json.meta.size.h));
+    let mut names = HashMap::default();
+    let mut frames = Vec::with_capacity(json.frames.0.len());
+    for (name, frame) in json.frames.0 {
+        if frame.rotated {
+            return Err(bevy::asset::Error::msg(format!(
+                "frame \"{name}\" is rotated, export the atlas without rotation"
+            )));
+        }
+        let rect = Rect::new(
+            frame.frame.x,
+            frame.frame.y,
+            frame.frame.x + frame.frame.w,
+            frame.frame.y + frame.frame.h,
+        );
+        let index = atlas.add_texture(rect);
+        names.insert(name, index);
+        frames.push(ImportedFrame {
+            source_size: frame
+                .source_size
+                .map(|size| Vec2::new(size.w, size.h))
+                .unwrap_or(rect.size()),
+            trim_offset: frame
+                .sprite_source_size
+                .map(|trimmed| Vec2::new(trimmed.x, trimmed.y))
+                .unwrap_or(Vec2::ZERO),
+            pivot: frame.pivot.map(|pivot| Vec2::new(pivot.x, pivot.y)),
+            duration: frame.duration.map(|milliseconds| milliseconds / 1000.),
+        });
+    }
+    let mut tags = HashMap::default();
+    for tag in json.meta.frame_tags {
+        if frames.len() <= tag.to || tag.to < tag.from {
+            return Err(bevy::asset::Error::msg(format!(
+                "tag \"{}\" has an invalid frame range {}..={}",
+                tag.name, tag.from, tag.to
+            )));
+        }
+        let direction = match tag.direction.as_str() {
+            "" | "forward" => TagDirection::Forward,
+            "reverse" => TagDirection::Reverse,
+            "pingpong" => TagDirection::PingPong,
+            "pingpong_reverse" => TagDirection::PingPongReverse,
+            direction => {
+                return Err(bevy::asset::Error::msg(format!(
+                    "tag \"{}\" has an unknown direction \"{direction}\"",
+                    tag.name
+                )));
+            }
+        };
+        tags.insert(
+            tag.name,
+            AtlasTag {
+                from: tag.from,
+                to: tag.to,
+                direction,
+            },
+        );
+    }
+
+    let atlas = load_context.set_labeled_asset(
+        "atlas",
+        LoadedAsset::new(atlas).with_dependency(texture_path),
+    );
+    let names =
+        load_context.set_labeled_asset("names", LoadedAsset::new(AtlasFrameNames { names }));
+    load_context.set_default_asset(LoadedAsset::new(ImportedAtlas {
+        atlas,
+        names,
+        frames,
+        tags,
+    }));
+    Ok(())
+}
+
+/// Loads the json data files exported by TexturePacker, in either its hash or array format
+#[derive(Default)]
+pub struct TexturePackerLoader;
+
+impl AssetLoader for TexturePackerLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move { load_json_atlas(bytes, load_context) })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["texturepacker.json"]
+    }
+}
+
+/// Loads the json data files exported by Aseprite, along with its frame durations and animation tags
+#[derive(Default)]
+pub struct AsepriteLoader;
+
+impl AssetLoader for AsepriteLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move { load_json_atlas(bytes, load_context) })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["aseprite.json"]
+    }
+}
+
+/// Plays an animation tag of an [`ImportedAtlas`] by setting the index of the entity's [`ExactAtlasImage`],
+/// or of the [`ImageSource::Atlas`](crate::source::ImageSource::Atlas) of its [`ExactSourceImage`] or [`ExactSprite`]
+#[derive(Component, Clone, Default, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default)]
+pub struct AtlasTagAnimation {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub atlas: Handle<ImportedAtlas>,
+    /// Name of the tag
+    pub tag: String,
+    /// Seconds since the animation started, reset to zero to restart it
+    pub elapsed: f32,
+    /// Duration of frames that don't have their own, in seconds
+    pub frame_duration: f32,
+}
+
+impl AtlasTagAnimation {
+    pub fn new(atlas: Handle<ImportedAtlas>, tag: impl Into<String>) -> Self {
+        Self {
+            atlas,
+            tag: tag.into(),
+            elapsed: 0.,
+            frame_duration: 0.1,
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn animate_atlas_tags(
+    time: Res<Time>,
+    imported_atlases: Res<Assets<ImportedAtlas>>,
+    mut animations: Query<(
+        &mut AtlasTagAnimation,
+        Option<&mut ExactAtlasImage>,
+        Option<&mut ExactSourceImage>,
+        Option<&mut ExactSprite>,
+    )>,
+) {
+    for (mut animation, atlas_image, source_image, sprite) in animations.iter_mut() {
+        animation.elapsed += time.delta_seconds();
+        let Some(imported) = imported_atlases.get(&animation.atlas) else {
+            continue;
+        };
+        let Some(tag) = imported.tags.get(&animation.tag) else {
+            continue;
+        };
+        let frames = tag.frames();
+        let duration_of = |index: usize| {
+            imported.frames[index]
+                .duration
+                .unwrap_or(animation.frame_duration)
+                .max(f32::EPSILON)
+        };
+        let total: f32 = frames.iter().map(|&index| duration_of(index)).sum();
+        let mut remaining = animation.elapsed % total;
+        let mut current = frames[0];
+        for &index in &frames {
+            current = index;
+            remaining -= duration_of(index);
+            if remaining < 0. {
+                break;
+            }
+        }
+        match (atlas_image, source_image, sprite) {
+            (Some(mut atlas_image), ..) => {
+                set_atlas_index(&mut atlas_image, |image| Some(&mut image.index), current);
+            }
+            (None, Some(mut source_image), _) => {
+                set_atlas_index(
+                    &mut source_image,
+                    |image| image.source.atlas_index_mut(),
+                    current,
+                );
+            }
+            (None, None, Some(mut sprite)) => {
+                set_atlas_index(
+                    &mut sprite,
+                    |sprite| sprite.source.atlas_index_mut(),
+                    current,
+                );
+            }
+            (None, None, None) => {}
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use bevy::prelude::*;
+use bevy::render::Extract;
+use bevy::ui::ExtractedUiNode;
+use bevy::ui::ExtractedUiNodes;
+use bevy::ui::UiStack;
+
+use crate::diagnostics::placeholder_node;
+use crate::diagnostics::InvalidImageFallback;
+use crate::source::ImageGeometry;
+use crate::ExactSize;
+use crate::ImageAlignment;
+
+/// The texture drawn by an [`ImageLayer`]
+#[derive(Clone, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayerImage {
+    /// The whole of a texture
+    Texture(#[cfg_attr(feature = "serde", serde(skip))] Handle<Image>),
+    /// A single frame of a texture atlas.
+    ///
+    /// The frame is drawn from its packed rect, as layers don't use the names,
+    /// trimmed borders or pivots of an [`ImportedAtlas`](crate::import::ImportedAtlas).
+    Atlas {
+        #[cfg_attr(feature = "serde", serde(skip))]
+        atlas: Handle<TextureAtlas>,
+        index: usize,
+    },
+}
+
+impl Default for LayerImage {
+    fn default() -> Self {
+        LayerImage::Texture(Default::default())
+    }
+}
+
+impl From<Handle<Image>> for LayerImage {
+    fn from(texture: Handle<Image>) -> Self {
+        LayerImage::Texture(texture)
+    }
+}
+
+/// A single image drawn by [`ExactImageLayers`]
+#[derive(Clone, Default, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageLayer {
+    pub image: LayerImage,
+    /// Layers don't affect the layout of their node,
+    /// so `AttemptPreserveAspectRatio` fills the node like `FillNode`
+    pub size: ExactSize,
+    pub alignment: ImageAlignment,
+    /// rotation of the image in radians
+    pub rotation: Option<f32>,
+    pub color: Color,
+}
+
+/// A stack of images drawn within a single ui node, from first to last.
+///
+/// The layers are drawn over the node's background color and exact image, and beneath its children,
+/// for example to build an item slot from a frame, an icon, a glow and a badge.
+#[derive(Component, Clone, Default, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default)]
+pub struct ExactImageLayers {
+    pub layers: Vec<ImageLayer>,
+}
+
+impl ExactImageLayers {
+    pub fn new(layers: impl IntoIterator<Item = ImageLayer>) -> Self {
+        Self {
+            layers: layers.into_iter().collect(),
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn extract_exact_image_layers(
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    images: Extract<Res<Assets<Image>>>,
+    texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
+    fallback: Extract<Res<InvalidImageFallback>>,
+    ui_scale: Extract<Res<UiScale>>,
+    ui_stack: Extract<Res<UiStack>>,
+    uinode_query: Extract<
+        Query<(
+            &Node,
+            &Style,
+            &ExactImageLayers,
+            &GlobalTransform,
+            &ComputedVisibility,
+            Option<&CalculatedClip>,
+        )>,
+    >,
+) {
+    let scale_factor = ui_scale.scale as f32;
+    for (stack_index, entity) in ui_stack.uinodes.iter().enumerate() {
+        if let Ok((node, style, layers, transform, visibility, clip)) = uinode_query.get(*entity) {
+            if !visibility.is_visible() {
+                continue;
+            }
+            for layer in &layers.layers {
+                if layer.color.a() == 0. {
+                    continue;
+                }
+                let (image, rect, atlas_size) = match &layer.image {
+                    LayerImage::Texture(texture) => match images.get(texture) {
+                        Some(image) => (
+                            texture.clone_weak(),
+                            Rect {
+                                min: Vec2::ZERO,
+                                max: image.size(),
+                            },
+                            None,
+                        ),
+                        None => continue,
+                    },
+                    LayerImage::Atlas { atlas, index } => {
+                        let Some(texture_atlas) = texture_atlases.get(atlas) else {
+                            continue;
+                        };
+                        if !images.contains(&texture_atlas.texture) {
+                            continue;
+                        }
+                        let Some(rect) = fallback.atlas_rect(texture_atlas, *index) else {
+                            if **fallback == InvalidImageFallback::Placeholder {
+                                extracted_uinodes.uinodes.push(placeholder_node(
+                                    stack_index,
+                                    node,
+                                    transform,
+                                    clip,
+                                    scale_factor,
+                                ));
+                            }
+                            continue;
+                        };
+                        (
+                            texture_atlas.texture.clone_weak(),
+                            rect,
+                            Some(texture_atlas.size),
+                        )
+                    }
+                };
+
+                let ImageGeometry {
+                    size,
+                    mut transform,
+                    ..
+                } = ImageGeometry::local(
+                    layer.size,
+                    layer.alignment,
+                    layer.rotation,
+                    None,
+                    node.size(),
+                    style.flex_direction,
+                    rect.size(),
+                    scale_factor,
+                )
+                .transformed(transform.compute_matrix());
+                // match the geometry of `ExactImage` and `ExactAtlasImage`
+                let rect = if atlas_size.is_some() {
+                    transform *= Mat4::from_scale((size / rect.size()).extend(1.));
+                    rect
+                } else {
+                    Rect {
+                        min: Vec2::ZERO,
+                        max: size,
+                    }
+                };
+
+                extracted_uinodes.uinodes.push(ExtractedUiNode {
+                    stack_index,
+                    transform,
+                    background_color: layer.color,
+                    rect,
+                    image,
+                    atlas_size,
+                    clip: clip.map(|clip| clip.clip),
+                    scale_factor,
+                });
+            }
+        }
+    }
+}
@@ -1,25 +1,102 @@
+use atlas::resolve_named_atlas_frames;
+use bevy::asset::HandleId;
+use bevy::asset::LoadState;
+use bevy::ecs::system::AsSystemLabel;
 use bevy::prelude::*;
 use bevy::render::Extract;
 use bevy::render::RenderApp;
 use bevy::render::RenderStage;
+use bevy::sprite::SpriteSystem;
 use bevy::ui::ExtractedUiNode;
 use bevy::ui::ExtractedUiNodes;
 use bevy::ui::FocusPolicy;
 use bevy::ui::RenderUiSystem;
 use bevy::ui::UiStack;
 use bevy::ui::UiSystem;
+use bevy::utils::HashMap;
+use bevy::utils::HashSet;
+use descriptor::advance_descriptor_animations;
+use descriptor::apply_image_descriptors;
+use descriptor::sync_descriptor_sources;
+use descriptor::ExactImageDescriptor;
+use diagnostics::placeholder_node;
+use diagnostics::validate_exact_atlas_images;
+use diagnostics::ExactImageWarning;
+use diagnostics::InvalidImageFallback;
+use import::animate_atlas_tags;
+use import::ImportedAtlas;
+use layers::extract_exact_image_layers;
+use placeholder::LoadingPlaceholder;
+use reload::mark_modified_exact_images;
+use render::ExtractedExactImage;
+use render::ExtractedExactImages;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use source::sync_exact_image_sources;
+use source::ExactSourceImage;
+use source::ImageGeometry;
+use source::ImageSource;
+use source::SourceRect;
+use sprite::extract_exact_sprites;
+
+pub mod atlas;
+pub mod builder;
+pub mod debug;
+pub mod descriptor;
+pub mod diagnostics;
+pub mod import;
+pub mod layers;
+pub mod placeholder;
+#[cfg(feature = "rasterizer")]
+pub mod raster;
+pub mod reload;
+pub mod render;
+pub mod source;
+pub mod sprite;
+pub mod ui_image;
 
 pub mod prelude {
+    pub use crate::atlas::AtlasFrameNames;
+    pub use crate::atlas::NamedAtlasFrame;
+    pub use crate::builder::SpawnExactImage;
+    pub use crate::debug::ExactImageDebug;
+    pub use crate::debug::ExactImageDebugPlugin;
+    pub use crate::debug::ExactImageDebugSettings;
+    pub use crate::descriptor::DescriptorAnimation;
+    pub use crate::descriptor::ExactImageDescriptor;
+    pub use crate::diagnostics::ExactImageWarning;
+    pub use crate::diagnostics::InvalidImageFallback;
+    pub use crate::import::AtlasTagAnimation;
+    pub use crate::import::ImportedAtlas;
+    pub use crate::layers::ExactImageLayers;
+    pub use crate::layers::ImageLayer;
+    pub use crate::layers::LayerImage;
+    pub use crate::placeholder::LoadingPlaceholder;
+    pub use crate::source::ExactSourceImage;
+    pub use crate::source::ExactSourceImageBundle;
+    pub use crate::source::ImageSource;
+    pub use crate::sprite::ExactSprite;
+    pub use crate::sprite::ExactSpriteBundle;
+    pub use crate::ui_image::ReplaceUiImage;
     pub use crate::ExactAtlasImage;
     pub use crate::ExactAtlasImageBundle;
     pub use crate::ExactImage;
     pub use crate::ExactImageBundle;
+    pub use crate::ExactImageFailed;
+    pub use crate::ExactImageLoaded;
     pub use crate::ExactImagePlugin;
     pub use crate::ExactSize;
     pub use crate::ImageAlignment;
+    pub use crate::ImageBlendMode;
+    pub use crate::ImageEffects;
+    pub use crate::ImageOutline;
+    pub use crate::ImageSampling;
+    pub use crate::ImageShadow;
 }
 
-#[derive(Copy, Clone, Default, Reflect)]
+#[derive(Copy, Clone, Default, PartialEq, Reflect, FromReflect, Serialize, Deserialize)]
+#[reflect(Default, Serialize, Deserialize)]
 pub enum ExactSize {
     #[default]
     /// The ui will attempt to size the node to preserve the aspect ratio of the image
@@ -31,13 +108,14 @@ pub enum ExactSize {
     /// Use the size of the source texture, regardless of the size of the node
     Texture,
     /// Use the size of the source texture scaled by the given factor, regardless of the size of the node
-    Scaled(Vec2),
+    Scaled(#[serde(deserialize_with = "deserialize_vec2")] Vec2),
     /// Use a custom size, regardless of the size of the node
-    Exactly(Vec2),
+    Exactly(#[serde(deserialize_with = "deserialize_vec2")] Vec2),
 }
 
 /// Alignment of the image within the node
-#[derive(Copy, Clone, Default, Reflect)]
+#[derive(Copy, Clone, Default, PartialEq, Reflect, FromReflect, Serialize, Deserialize)]
+#[reflect(Default, Serialize, Deserialize)]
 pub enum ImageAlignment {
     TopLeft,
     TopCenter,
@@ -49,21 +127,247 @@ pub enum ImageAlignment {
     BottomLeft,
     BottomCenter,
     BottomRight,
-    Anchor(Vec2),
+    Anchor(#[serde(deserialize_with = "deserialize_vec2")] Vec2),
+}
+
+/// Reads a [`Vec2`] written either by serde as `(x, y)`, or field by field as scenes write it
+fn deserialize_vec2<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec2, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum AnyVec2 {
+        Tuple(f32, f32),
+        Struct { x: f32, y: f32 },
+    }
+    Ok(match AnyVec2::deserialize(deserializer)? {
+        AnyVec2::Tuple(x, y) | AnyVec2::Struct { x, y } => Vec2::new(x, y),
+    })
+}
+
+impl ExactSize {
+    /// The size of an image within a node, given the size of its source texture rect
+    pub(crate) fn compute(
+        self,
+        node_size: Vec2,
+        flex_direction: FlexDirection,
+        source_size: Vec2,
+        scale_factor: f32,
+    ) -> Vec2 {
+        let mut size = node_size;
+        match self {
+            ExactSize::ForcePreserveAspectRatio => {
+                let aspect = source_size.y / source_size.x;
+                match flex_direction {
+                    FlexDirection::Column | FlexDirection::ColumnReverse => {
+                        size.x = size.y / aspect;
+                    }
+                    FlexDirection::Row | FlexDirection::RowReverse => {
+                        size.y = size.x * aspect;
+                    }
+                }
+            }
+            ExactSize::Texture => size = source_size * scale_factor,
+            ExactSize::Scaled(scale) => size = scale * source_size * scale_factor,
+            ExactSize::Exactly(custom_size) => size = custom_size * scale_factor,
+            _ => {}
+        }
+        size
+    }
+}
+
+impl ImageAlignment {
+    /// Offset from the center of the node to the center of an image of the given size
+    pub(crate) fn offset(self, node_size: Vec2, size: Vec2) -> Vec2 {
+        use ImageAlignment::*;
+        Vec2 {
+            x: match self {
+                TopLeft | CenterLeft | BottomLeft => 0.5 * (-node_size.x + size.x),
+                TopCenter | Center | BottomCenter => 0.,
+                TopRight | CenterRight | BottomRight => 0.5 * (node_size.x - size.x),
+                Anchor(Vec2 { x, .. }) => x * node_size.x,
+            },
+            y: match self {
+                TopLeft | TopCenter | TopRight => 0.5 * (-node_size.y + size.y),
+                CenterLeft | Center | CenterRight => 0.,
+                BottomLeft | BottomCenter | BottomRight => 0.5 * (node_size.y - size.y),
+                Anchor(Vec2 { y, .. }) => y * 0.5 * node_size.y,
+            },
+        }
+    }
+}
+
+/// Transform of an image within a node, relative to the origin of the ui
+pub(crate) fn image_transform(
+    node_transform: &GlobalTransform,
+    alignment_offset: Vec2,
+    rotation: Option<f32>,
+) -> Mat4 {
+    let mut transform = node_transform.compute_matrix();
+    transform *= Mat4::from_translation(alignment_offset.extend(0.));
+    if let Some(rotation) = rotation {
+        transform *= Mat4::from_rotation_z(rotation);
+    }
+    transform
+}
+
+/// How the image is blended with the ui drawn beneath it
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[reflect(Default)]
+pub enum ImageBlendMode {
+    #[default]
+    /// Standard alpha blending
+    Alpha,
+    /// The colors of the image have already been multiplied by their alpha
+    PremultipliedAlpha,
+    /// The image is added to the ui beneath it, for glows and highlights
+    Additive,
+    /// The image is multiplied with the ui beneath it, darkening it
+    Multiply,
+    /// The inverted image is multiplied with the inverted ui beneath it, lightening it
+    Screen,
+}
+
+/// Overrides how the texture of an exact image is sampled, instead of using the texture's own sampler.
+///
+/// Pixel art and smooth images can be drawn side by side without changing the default
+/// sampler of the `ImagePlugin`. Images with a sampling override can't be batched with the rest of the ui.
+#[derive(Component, Copy, Clone, Default, PartialEq, Eq, Hash, Debug, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[reflect(Component, Default)]
+pub enum ImageSampling {
+    /// Sharp texels, for pixel art
+    Nearest,
+    /// Smoothly interpolated texels
+    #[default]
+    Linear,
+    /// Smoothly interpolated texels and mip levels, for textures drawn smaller than their size.
+    ///
+    /// Textures without mip levels are sampled like `Linear`.
+    LinearMipmapped,
+}
+
+/// Color adjustments applied to an exact image when it is drawn.
+///
+/// Add alongside an [`ExactImage`] or [`ExactAtlasImage`],
+/// for example to desaturate the icons of disabled buttons.
+#[derive(Component, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[reflect(Component, Default)]
+pub struct ImageEffects {
+    /// Amount of desaturation, from 0 (unchanged) to 1 (fully gray)
+    pub grayscale: f32,
+    /// Multiplier of the image's colors, 1 is unchanged
+    pub brightness: f32,
+    /// 1 is unchanged, 0 is flat gray
+    pub contrast: f32,
+    /// 1 is unchanged, 0 is fully desaturated and values above 1 oversaturate
+    pub saturation: f32,
+    /// Rotation of the hue in radians
+    pub hue_rotation: f32,
+}
+
+impl Default for ImageEffects {
+    fn default() -> Self {
+        Self {
+            grayscale: 0.,
+            brightness: 1.,
+            contrast: 1.,
+            saturation: 1.,
+            hue_rotation: 0.,
+        }
+    }
+}
+
+/// An outline drawn around the opaque pixels of an exact image.
+///
+/// The outline is alpha blended, whatever the blend mode of the image.
+#[derive(Component, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[reflect(Component, Default)]
+pub struct ImageOutline {
+    pub color: Color,
+    /// Width of the outline in pixels
+    pub width: f32,
+}
+
+impl Default for ImageOutline {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            width: 1.,
+        }
+    }
+}
+
+impl ImageOutline {
+    fn scaled(self, scale_factor: f32) -> Self {
+        Self {
+            width: self.width * scale_factor,
+            ..self
+        }
+    }
+}
+
+/// A drop shadow drawn beneath an exact image, in the shape of its opaque pixels.
+///
+/// The shadow is rotated and aligned along with the image,
+/// and is alpha blended whatever the blend mode of the image.
+#[derive(Component, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[reflect(Component, Default)]
+pub struct ImageShadow {
+    pub color: Color,
+    /// Offset of the shadow from the image in pixels
+    pub offset: Vec2,
+    /// Radius of the shadow's blur in pixels, zero for a hard shadow
+    pub blur_radius: f32,
+}
+
+impl Default for ImageShadow {
+    fn default() -> Self {
+        Self {
+            color: Color::rgba(0., 0., 0., 0.5),
+            offset: Vec2::new(4., 4.),
+            blur_radius: 4.,
+        }
+    }
+}
+
+impl ImageShadow {
+    fn scaled(self, scale_factor: f32) -> Self {
+        Self {
+            offset: self.offset * scale_factor,
+            blur_radius: self.blur_radius * scale_factor,
+            ..self
+        }
+    }
 }
 
 #[derive(Component, Default, Reflect)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[reflect(Component, Default)]
 pub struct ExactImage {
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub texture: Handle<Image>,
     /// rotation of the image in radians
     pub rotation: Option<f32>,
     pub alignment: ImageAlignment,
     pub color: Color,
     pub size: ExactSize,
+    /// How the image is blended with the ui drawn beneath it
+    pub blend_mode: ImageBlendMode,
+    /// Drawn in place of the image until its texture has loaded
+    pub loading_placeholder: Option<LoadingPlaceholder>,
+    /// Size of the texture assumed by the layout until it has loaded,
+    /// so that the node doesn't change size once it has
+    pub placeholder_size: Option<Vec2>,
 }
 
 #[derive(Component, Default, Reflect)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[reflect(Component, Default)]
 pub struct ExactAtlasImage {
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub atlas: Handle<TextureAtlas>,
     pub index: usize,
     /// rotation of the image in radians
@@ -71,6 +375,19 @@ pub struct ExactAtlasImage {
     pub alignment: ImageAlignment,
     pub color: Color,
     pub size: ExactSize,
+    /// How the image is blended with the ui drawn beneath it
+    pub blend_mode: ImageBlendMode,
+    /// Drawn in place of the image until its texture has loaded
+    pub loading_placeholder: Option<LoadingPlaceholder>,
+    /// Size of the texture assumed by the layout until it has loaded,
+    /// so that the node doesn't change size once it has
+    pub placeholder_size: Option<Vec2>,
+    /// Layout of the atlas frames before they were packed.
+    ///
+    /// Trimmed frames are sized and aligned as if they were untrimmed,
+    /// so the image doesn't jitter as its frames change.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub frames: Option<Handle<ImportedAtlas>>,
 }
 
 #[derive(Bundle)]
@@ -167,272 +484,440 @@ impl Default for ExactAtlasImageBundle {
     }
 }
 
-#[allow(clippy::type_complexity)]
-pub fn exact_image_system(
-    mut commands: Commands,
-    textures: Res<Assets<Image>>,
-    images: Query<(Entity, &ExactImage), (Without<UiImage>, Without<Text>)>,
-    mut calculated_sizes: Query<&mut CalculatedSize>,
+/// Sent once the texture of an [`ExactImage`], or the atlas and texture of an [`ExactAtlasImage`], is available
+#[derive(Clone, Debug)]
+pub struct ExactImageLoaded {
+    pub entity: Entity,
+    /// Size of the image's source texture or atlas frame,
+    /// before trimming for frames of an [`ImportedAtlas`]
+    pub size: Vec2,
+}
+
+/// Sent if the texture or atlas of an exact image fails to load
+#[derive(Clone, Debug)]
+pub struct ExactImageFailed {
+    pub entity: Entity,
+    /// The asset that failed to load
+    pub handle: HandleUntyped,
+}
+
+/// Exact images waiting on their assets, and the assets whose load has been reported
+#[derive(Default)]
+pub struct LoadTracker {
+    pending: HashSet<Entity>,
+    reported: HashMap<Entity, HandleId>,
+    /// Images drawn from render targets, which are checked every frame as they can be resized at any time
+    render_targets: HashSet<Entity>,
+}
+
+/// Sizes the node of an image with the given source size, as set by its `ExactSize`
+fn update_calculated_size(
+    commands: &mut Commands,
+    calculated_sizes: &mut Query<&mut CalculatedSize>,
+    id: Entity,
+    exact_size: ExactSize,
+    texture_size: Vec2,
 ) {
-    for (id, image) in images.iter() {
-        if let Some(texture) = textures.get(&image.texture) {
-            match (image.size, calculated_sizes.get_mut(id)) {
-                (
-                    ExactSize::AttemptPreserveAspectRatio | ExactSize::ForcePreserveAspectRatio,
-                    Ok(mut calculated_size),
-                ) => {
-                    let texture_size = texture.size();
-                    let size = Size::new(Val::Px(texture_size.x), Val::Px(texture_size.y));
-                    if size != calculated_size.size {
-                        calculated_size.size = size;
-                    }
-                }
-                (
-                    ExactSize::AttemptPreserveAspectRatio | ExactSize::ForcePreserveAspectRatio,
-                    Err(_),
-                ) => {
-                    let texture_size = texture.size();
-                    let size = Size::new(Val::Px(texture_size.x), Val::Px(texture_size.y));
-                    commands.entity(id).insert(CalculatedSize { size });
-                }
-                (_, Ok(_)) => {
-                    commands.entity(id).remove::<CalculatedSize>();
-                }
-                _ => {}
+    match (exact_size, calculated_sizes.get_mut(id)) {
+        (
+            ExactSize::AttemptPreserveAspectRatio | ExactSize::ForcePreserveAspectRatio,
+            Ok(mut calculated_size),
+        ) => {
+            let size = Size::new(Val::Px(texture_size.x), Val::Px(texture_size.y));
+            if size != calculated_size.size {
+                calculated_size.size = size;
             }
         }
+        (ExactSize::AttemptPreserveAspectRatio | ExactSize::ForcePreserveAspectRatio, Err(_)) => {
+            let size = Size::new(Val::Px(texture_size.x), Val::Px(texture_size.y));
+            commands.entity(id).insert(CalculatedSize { size });
+        }
+        (_, Ok(_)) => {
+            commands.entity(id).remove::<CalculatedSize>();
+        }
+        _ => {}
     }
 }
 
-#[allow(clippy::type_complexity)]
-pub fn exact_atlas_image_system(
+/// Updates the layout of exact images that have changed, or are waiting for their assets to load.
+///
+/// Modified textures and atlases are picked up through [`reload::mark_modified_exact_images`],
+/// so images that are loaded and unchanged cost nothing.
+/// The size of a [`ImageSource::RenderTarget`] is checked every frame instead,
+/// so its node is resized in the same frame as its target.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn exact_image_system(
     mut commands: Commands,
+    textures: Res<Assets<Image>>,
     atlases: Res<Assets<TextureAtlas>>,
-    images: Query<(Entity, &ExactAtlasImage), (Without<UiImage>, Without<Text>)>,
-    mut calculated_sizes: Query<&mut CalculatedSize>,
+    imported_atlases: Res<Assets<ImportedAtlas>>,
+    descriptors: Res<Assets<ExactImageDescriptor>>,
+    fallback: Res<InvalidImageFallback>,
+    asset_server: Res<AssetServer>,
+    mut tracker: Local<LoadTracker>,
+    mut loaded_events: EventWriter<ExactImageLoaded>,
+    mut failed_events: EventWriter<ExactImageFailed>,
+    removed: RemovedComponents<ExactSourceImage>,
+    mut layout: ParamSet<(
+        Query<
+            Entity,
+            (
+                With<ExactSourceImage>,
+                Or<(Changed<ExactSourceImage>, Added<CalculatedSize>)>,
+            ),
+        >,
+        Query<&mut CalculatedSize>,
+    )>,
+    images: Query<(Entity, &ExactSourceImage), (Without<UiImage>, Without<Text>)>,
 ) {
-    for (id, atlas_image) in images.iter() {
-        if let Some(texture_atlas) = atlases.get(&atlas_image.atlas) {
-            match (atlas_image.size, calculated_sizes.get_mut(id)) {
-                (
-                    ExactSize::AttemptPreserveAspectRatio | ExactSize::ForcePreserveAspectRatio,
-                    Ok(mut calculated_size),
-                ) => {
-                    let texture_size = texture_atlas.textures[atlas_image.index].size();
-                    let size = Size::new(Val::Px(texture_size.x), Val::Px(texture_size.y));
-                    if size != calculated_size.size {
-                        calculated_size.size = size;
-                    }
-                }
-                (
-                    ExactSize::AttemptPreserveAspectRatio | ExactSize::ForcePreserveAspectRatio,
-                    Err(_),
-                ) => {
-                    let texture_size = texture_atlas.textures[atlas_image.index].size();
-                    let size = Size::new(Val::Px(texture_size.x), Val::Px(texture_size.y));
-                    commands.entity(id).insert(CalculatedSize { size });
-                }
-                (_, Ok(_)) => {
-                    commands.entity(id).remove::<CalculatedSize>();
-                }
-                _ => {}
+    let LoadTracker {
+        pending,
+        reported,
+        render_targets,
+    } = &mut *tracker;
+    for entity in removed.iter() {
+        pending.remove(&entity);
+        reported.remove(&entity);
+        render_targets.remove(&entity);
+    }
+    if fallback.is_changed() {
+        pending.extend(images.iter().map(|(id, _)| id));
+    } else {
+        pending.extend(layout.p0().iter());
+    }
+    pending.extend(render_targets.iter().copied());
+    pending.retain(|&id| {
+        let Ok((_, image)) = images.get(id) else {
+            render_targets.remove(&id);
+            return false;
+        };
+        if matches!(image.source, ImageSource::RenderTarget(_)) {
+            render_targets.insert(id);
+        } else {
+            render_targets.remove(&id);
+        }
+        let loaded = image.source.is_loaded(&textures, &atlases, &descriptors);
+        let failed = image
+            .source
+            .failed(&asset_server, &textures, &atlases, &descriptors);
+        let rect = image
+            .source
+            .rect(&textures, &atlases, &descriptors, *fallback);
+        let frames_loading = matches!(
+            &image.frames,
+            Some(frames) if !imported_atlases.contains(frames)
+                && asset_server.get_load_state(frames) != LoadState::Failed
+        );
+        // trimmed frames are sized as if untrimmed, once their imported atlas has loaded
+        let source_size = match rect {
+            SourceRect::Ready(rect) => Some(
+                image
+                    .imported_frame(&imported_atlases)
+                    .map_or(rect.size(), |frame| frame.source_size),
+            ),
+            SourceRect::Loading | SourceRect::Invalid => None,
+        };
+        if reported.get(&id) != Some(&image.source.id()) {
+            if let (true, false, Some(size)) = (loaded, frames_loading, source_size) {
+                reported.insert(id, image.source.id());
+                loaded_events.send(ExactImageLoaded { entity: id, size });
+            } else if let Some(handle) = failed.clone() {
+                reported.insert(id, image.source.id());
+                failed_events.send(ExactImageFailed { entity: id, handle });
             }
         }
-    }
+        let texture_size = match rect {
+            SourceRect::Loading => image.placeholder_size,
+            SourceRect::Ready(_) | SourceRect::Invalid => source_size,
+        };
+        if let Some(texture_size) = texture_size {
+            update_calculated_size(
+                &mut commands,
+                &mut layout.p1(),
+                id,
+                image.size,
+                texture_size,
+            );
+        }
+        (!loaded && failed.is_none()) || frames_loading
+    });
 }
 
-#[allow(clippy::type_complexity)]
+/// Atlas images are laid out by [`exact_image_system`], which is labeled with this system
+/// so that systems ordered against it keep their order
+#[deprecated(note = "atlas images are laid out by `exact_image_system`")]
+pub fn exact_atlas_image_system() {}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn extract_exact_images(
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    mut extracted_exact_images: ResMut<ExtractedExactImages>,
     images: Extract<Res<Assets<Image>>>,
+    texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
+    imported_atlases: Extract<Res<Assets<ImportedAtlas>>>,
+    descriptors: Extract<Res<Assets<ExactImageDescriptor>>>,
+    fallback: Extract<Res<InvalidImageFallback>>,
+    time: Extract<Res<Time>>,
     ui_scale: Extract<Res<UiScale>>,
     ui_stack: Extract<Res<UiStack>>,
     uinode_query: Extract<
         Query<(
             &Node,
             &Style,
-            &ExactImage,
+            &ExactSourceImage,
             &GlobalTransform,
             &ComputedVisibility,
             Option<&CalculatedClip>,
+            Option<&ImageEffects>,
+            Option<&ImageOutline>,
+            Option<&ImageShadow>,
+            Option<&ImageSampling>,
         )>,
     >,
 ) {
     let scale_factor = ui_scale.scale as f32;
     for (stack_index, entity) in ui_stack.uinodes.iter().enumerate() {
-        if let Ok((node, style, image, transform, visibility, clip)) = uinode_query.get(*entity) {
-            if !visibility.is_visible() || image.color.a() == 0. || !images.contains(&image.texture)
-            {
-                continue;
+        let Ok((
+            node,
+            style,
+            image,
+            transform,
+            visibility,
+            clip,
+            effects,
+            outline,
+            shadow,
+            sampling,
+        )) = uinode_query.get(*entity)
+        else {
+            continue;
+        };
+        if !visibility.is_visible() || image.color.a() == 0. {
+            continue;
+        }
+        let (Some(texture), Some(texture_size)) = (
+            image
+                .source
+                .texture(&texture_atlases, &descriptors)
+                .filter(|texture| images.contains(texture)),
+            image
+                .source
+                .texture_size(&images, &texture_atlases, &descriptors),
+        ) else {
+            if let Some(placeholder) = &image.loading_placeholder {
+                let size = image.size.compute(
+                    node.size(),
+                    style.flex_direction,
+                    image.placeholder_size.unwrap_or(node.size()),
+                    scale_factor,
+                );
+                let alignment_offset = image.alignment.offset(node.size(), size);
+                extracted_uinodes.uinodes.extend(placeholder.extract(
+                    &images,
+                    &texture_atlases,
+                    time.elapsed_seconds(),
+                    stack_index,
+                    image_transform(transform, alignment_offset, image.rotation),
+                    size,
+                    clip,
+                    scale_factor,
+                ));
             }
-            let mut transform = transform.compute_matrix();
-
-            let mut size = node.size();
-            match image.size {
-                ExactSize::ForcePreserveAspectRatio => {
-                    if matches!(
-                        style.flex_direction,
-                        FlexDirection::Column | FlexDirection::ColumnReverse
-                    ) {
-                        size.x = size.y / images.get(&image.texture).unwrap().aspect_2d();
-                    } else {
-                        size.y = size.x * images.get(&image.texture).unwrap().aspect_2d();
-                    }
-                }
-                ExactSize::Texture => {
-                    size = images.get(&image.texture).unwrap().size() * scale_factor
-                }
-                ExactSize::Scaled(scale) => {
-                    size = scale * images.get(&image.texture).unwrap().size() * scale_factor
-                }
-                ExactSize::Exactly(custom_size) => size = custom_size * scale_factor,
-                _ => {}
+            continue;
+        };
+        let SourceRect::Ready(rect) =
+            image
+                .source
+                .rect(&images, &texture_atlases, &descriptors, **fallback)
+        else {
+            if **fallback == InvalidImageFallback::Placeholder {
+                extracted_uinodes.uinodes.push(placeholder_node(
+                    stack_index,
+                    node,
+                    transform,
+                    clip,
+                    scale_factor,
+                ));
             }
-
-            use ImageAlignment::*;
-            let alignment_offset = Vec2 {
-                x: match image.alignment {
-                    TopLeft | CenterLeft | BottomLeft => 0.5 * (-node.size().x + size.x),
-                    TopCenter | Center | BottomCenter => 0.,
-                    TopRight | CenterRight | BottomRight => 0.5 * (node.size().x - size.x),
-                    Anchor(Vec2 { x, .. }) => x * node.size().x,
-                },
-                y: match image.alignment {
-                    TopLeft | TopCenter | TopRight => 0.5 * (-node.size().y + size.y),
-                    CenterLeft | Center | CenterRight => 0.,
-                    BottomLeft | BottomCenter | BottomRight => 0.5 * (node.size().y - size.y),
-                    Anchor(Vec2 { y, .. }) => y * 0.5 * node.size().y,
-                },
-            };
-
-            transform *= Mat4::from_translation(alignment_offset.extend(0.));
-            if let Some(rotation) = image.rotation {
-                transform *= Mat4::from_rotation_z(rotation);
+            continue;
+        };
+        let frame = image.imported_frame(&imported_atlases);
+        let source_size = frame.map_or(rect.size(), |frame| frame.source_size);
+        let ImageGeometry {
+            size,
+            mut transform,
+            ..
+        } = image.geometry(frame, node, style, transform, source_size, scale_factor);
+        let effects = effects.copied().unwrap_or_default();
+        let sampling = sampling.copied();
+        if let Some(nine_slice) = image.source.nine_slice(&descriptors) {
+            for (source, target) in nine_slice.slices(rect, size, scale_factor) {
+                extracted_exact_images.push_or_forward(
+                    &mut extracted_uinodes,
+                    ExtractedExactImage {
+                        node: ExtractedUiNode {
+                            stack_index,
+                            transform: transform
+                                * Mat4::from_translation(target.center().extend(0.))
+                                * Mat4::from_scale((target.size() / source.size()).extend(1.)),
+                            background_color: image.color,
+                            rect: source,
+                            image: texture.clone_weak(),
+                            atlas_size: Some(texture_size),
+                            clip: clip.map(|clip| clip.clip),
+                            scale_factor,
+                        },
+                        blend_mode: image.blend_mode,
+                        effects,
+                        outline: None,
+                        shadow: None,
+                        sampling,
+                        z: 0.,
+                        effects_z: 0.,
+                        order: 0,
+                    },
+                );
             }
-
-            extracted_uinodes.uinodes.push(ExtractedUiNode {
-                stack_index,
-                transform,
-                background_color: image.color,
-                rect: Rect {
+            continue;
+        }
+        // a whole texture is drawn at its final size rather than scaled,
+        // as bevy clips the texture coordinates of unscaled quads exactly
+        let (rect, atlas_size) = if image.source.is_whole_texture() {
+            (
+                Rect {
                     min: Vec2::ZERO,
                     max: size,
                 },
-                image: image.texture.clone_weak(),
-                atlas_size: None,
-                clip: clip.map(|clip| clip.clip),
-                scale_factor,
-            });
-        }
-    }
-}
-
-#[allow(clippy::type_complexity)]
-pub fn extract_exact_atlas_images(
-    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
-    images: Extract<Res<Assets<Image>>>,
-    texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
-    ui_scale: Extract<Res<UiScale>>,
-    ui_stack: Extract<Res<UiStack>>,
-    uinode_query: Extract<
-        Query<(
-            &Node,
-            &Style,
-            &ExactAtlasImage,
-            &GlobalTransform,
-            &ComputedVisibility,
-            Option<&CalculatedClip>,
-        )>,
-    >,
-) {
-    let scale_factor = ui_scale.scale as f32;
-    for (stack_index, entity) in ui_stack.uinodes.iter().enumerate() {
-        if let Ok((node, style, atlas_image, transform, visibility, clip)) =
-            uinode_query.get(*entity)
-        {
-            if !visibility.is_visible() || atlas_image.color.a() == 0. {
-                continue;
+                None,
+            )
+        } else {
+            let scale = size / source_size;
+            if let Some(frame) = frame {
+                let trimmed_center = frame.trimmed_center(rect.size());
+                transform *= Mat4::from_translation((scale * trimmed_center).extend(0.));
             }
-            if let Some(texture_atlas) = texture_atlases.get(&atlas_image.atlas) {
-                let image = texture_atlas.texture.clone_weak();
-                if !images.contains(&image) {
-                    continue;
-                }
-                let rect = texture_atlas.textures[atlas_image.index];
-                let mut transform = transform.compute_matrix();
-
-                let mut size = node.size();
-                match atlas_image.size {
-                    ExactSize::ForcePreserveAspectRatio => {
-                        let aspect = rect.size().y / rect.size().x;
-                        match style.flex_direction {
-                            FlexDirection::Column | FlexDirection::ColumnReverse => {
-                                size.x = size.y / aspect;
-                            }
-                            FlexDirection::Row | FlexDirection::RowReverse => {
-                                size.y = size.x * aspect;
-                            }
-                        }
-                    }
-                    ExactSize::Texture => size = rect.size() * scale_factor,
-                    ExactSize::Scaled(scale) => size = scale * rect.size() * scale_factor,
-                    ExactSize::Exactly(custom_size) => size = custom_size * scale_factor,
-                    _ => {}
-                }
-
-                use ImageAlignment::*;
-                let alignment_offset = Vec2 {
-                    x: match atlas_image.alignment {
-                        TopLeft | CenterLeft | BottomLeft => 0.5 * (-node.size().x + size.x),
-                        TopCenter | Center | BottomCenter => 0.,
-                        TopRight | CenterRight | BottomRight => 0.5 * (node.size().x - size.x),
-                        Anchor(Vec2 { x, .. }) => x * node.size().x,
-                    },
-                    y: match atlas_image.alignment {
-                        TopLeft | TopCenter | TopRight => 0.5 * (-node.size().y + size.y),
-                        CenterLeft | Center | CenterRight => 0.,
-                        BottomLeft | BottomCenter | BottomRight => 0.5 * (node.size().y - size.y),
-                        Anchor(Vec2 { y, .. }) => y * 0.5 * node.size().y,
-                    },
-                };
-
-                transform *= Mat4::from_translation(alignment_offset.extend(0.));
-                if let Some(rotation) = atlas_image.rotation {
-                    transform *= Mat4::from_rotation_z(rotation);
-                }
-                let scale = size / rect.size();
-                transform *= Mat4::from_scale(scale.extend(1.));
-                extracted_uinodes.uinodes.push(ExtractedUiNode {
+            transform *= Mat4::from_scale(scale.extend(1.));
+            (rect, Some(texture_size))
+        };
+        extracted_exact_images.push_or_forward(
+            &mut extracted_uinodes,
+            ExtractedExactImage {
+                node: ExtractedUiNode {
                     stack_index,
                     transform,
-                    background_color: atlas_image.color,
+                    background_color: image.color,
                     rect,
-                    image,
-                    atlas_size: Some(texture_atlas.size),
+                    image: texture.clone_weak(),
+                    atlas_size,
                     clip: clip.map(|clip| clip.clip),
                     scale_factor,
-                });
-            }
-        }
+                },
+                blend_mode: image.blend_mode,
+                effects,
+                outline: outline.map(|outline| outline.scaled(scale_factor)),
+                shadow: shadow.map(|shadow| shadow.scaled(scale_factor)),
+                sampling,
+                z: 0.,
+                effects_z: 0.,
+                order: 0,
+            },
+        );
     }
 }
 
+/// Atlas images are extracted by [`extract_exact_images`], which is labeled with this system
+/// so that systems ordered against it keep their order
+#[deprecated(note = "atlas images are extracted by `extract_exact_images`")]
+pub fn extract_exact_atlas_images() {}
+
+/// Runs before [`CoreStage::PostUpdate`], inserting the [`ExactSourceImage`] of each [`ExactImage`],
+/// [`ExactAtlasImage`] and descriptor handle so the image is laid out in the frame it's spawned
+#[derive(StageLabel)]
+pub struct ExactImageSyncStage;
+
 pub struct ExactImagePlugin;
 
 impl Plugin for ExactImagePlugin {
+    #[allow(deprecated)]
     fn build(&self, app: &mut App) {
         app.register_type::<ExactImage>()
             .register_type::<ExactAtlasImage>()
+            .register_type::<ExactSourceImage>()
+            .register_type::<source::ImageSource>()
             .register_type::<ExactSize>()
             .register_type::<ImageAlignment>()
+            .register_type::<ImageBlendMode>()
+            .register_type::<ImageEffects>()
+            .register_type::<ImageOutline>()
+            .register_type::<ImageShadow>()
+            .register_type::<ImageSampling>()
+            .register_type::<layers::ExactImageLayers>()
+            .register_type::<atlas::NamedAtlasFrame>()
+            .register_type::<InvalidImageFallback>()
+            .register_type::<LoadingPlaceholder>()
+            .register_type::<descriptor::DescriptorAnimation>()
+            .register_type::<import::AtlasTagAnimation>()
+            .register_type::<ui_image::ReplaceUiImage>()
+            .register_type::<sprite::ExactSprite>()
+            .register_type::<layers::ImageLayer>()
+            .register_type::<layers::LayerImage>()
+            .register_type::<Vec<layers::ImageLayer>>()
+            .register_type::<Option<f32>>()
+            .register_type::<Option<String>>()
+            .register_type::<Option<Vec2>>()
+            .register_type::<Option<LoadingPlaceholder>>()
+            .register_type::<Handle<TextureAtlas>>()
+            .register_type::<Handle<atlas::AtlasFrameNames>>()
+            .register_type::<Handle<import::ImportedAtlas>>()
+            .register_type::<Option<Handle<import::ImportedAtlas>>>()
+            .register_type::<Handle<ExactImageDescriptor>>()
+            .add_asset::<atlas::AtlasFrameNames>()
+            .add_asset::<ExactImageDescriptor>()
+            .init_asset_loader::<descriptor::ExactImageDescriptorLoader>()
+            .add_asset::<import::ImportedAtlas>()
+            .init_asset_loader::<import::TexturePackerLoader>()
+            .init_asset_loader::<import::AsepriteLoader>()
+            .init_resource::<InvalidImageFallback>()
+            .add_event::<ExactImageWarning>()
+            .add_event::<ExactImageLoaded>()
+            .add_event::<ExactImageFailed>()
             .add_system_to_stage(
                 CoreStage::PostUpdate,
-                exact_image_system.before(UiSystem::Flex),
+                mark_modified_exact_images
+                    .before(exact_image_system)
+                    .before(validate_exact_atlas_images)
+                    .before(apply_image_descriptors),
+            )
+            .add_stage_before(
+                CoreStage::PostUpdate,
+                ExactImageSyncStage,
+                SystemStage::parallel(),
+            )
+            .add_system_to_stage(
+                ExactImageSyncStage,
+                resolve_named_atlas_frames.before(sync_exact_image_sources),
             )
+            .add_system_to_stage(ExactImageSyncStage, sync_exact_image_sources)
+            .add_system_to_stage(ExactImageSyncStage, sync_descriptor_sources)
             .add_system_to_stage(
                 CoreStage::PostUpdate,
-                exact_atlas_image_system.before(UiSystem::Flex),
-            );
+                validate_exact_atlas_images.before(exact_image_system),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                apply_image_descriptors.before(exact_image_system),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                exact_image_system
+                    .label(exact_atlas_image_system.as_system_label())
+                    .before(UiSystem::Flex),
+            )
+            .add_system_to_stage(CoreStage::PreUpdate, ui_image::replace_ui_images)
+            .add_system(advance_descriptor_animations)
+            .add_system(animate_atlas_tags);
+
+        render::build_exact_image_render(app);
 
         let render_app = match app.get_sub_app_mut(RenderApp) {
             Ok(render_app) => render_app,
@@ -442,11 +927,17 @@ impl Plugin for ExactImagePlugin {
         render_app
             .add_system_to_stage(
                 RenderStage::Extract,
-                extract_exact_images.after(RenderUiSystem::ExtractNode),
+                extract_exact_images
+                    .label(extract_exact_atlas_images.as_system_label())
+                    .after(RenderUiSystem::ExtractNode),
+            )
+            .add_system_to_stage(
+                RenderStage::Extract,
+                extract_exact_image_layers.after(extract_exact_images),
             )
             .add_system_to_stage(
                 RenderStage::Extract,
-                extract_exact_atlas_images.after(RenderUiSystem::ExtractNode),
+                extract_exact_sprites.after(SpriteSystem::ExtractSprites),
             );
     }
 }
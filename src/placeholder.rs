@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy::ui::ExtractedUiNode;
+
+/// What an exact image draws until its texture has loaded
+#[derive(Clone, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LoadingPlaceholder {
+    /// Fill the image's rect with a color
+    Color(Color),
+    /// Draw another texture, which should be small enough to already be loaded
+    Texture(#[cfg_attr(feature = "serde", serde(skip))] Handle<Image>),
+    /// Loop through every frame of a texture atlas, for example a spinner
+    Animation {
+        #[cfg_attr(feature = "serde", serde(skip))]
+        atlas: Handle<TextureAtlas>,
+        frames_per_second: f32,
+    },
+}
+
+impl Default for LoadingPlaceholder {
+    fn default() -> Self {
+        LoadingPlaceholder::Color(Color::rgba(0.5, 0.5, 0.5, 0.5))
+    }
+}
+
+impl LoadingPlaceholder {
+    /// The ui node drawing the placeholder over the given image rect.
+    ///
+    /// Returns `None` if the placeholder's own texture hasn't loaded either.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn extract(
+        &self,
+        images: &Assets<Image>,
+        texture_atlases: &Assets<TextureAtlas>,
+        elapsed_seconds: f32,
+        stack_index: usize,
+        mut transform: Mat4,
+        size: Vec2,
+        clip: Option<&CalculatedClip>,
+        scale_factor: f32,
+    ) -> Option<ExtractedUiNode> {
+        let (background_color, image, rect, atlas_size) = match self {
+            LoadingPlaceholder::Color(color) => (
+                *color,
+                DEFAULT_IMAGE_HANDLE.typed().clone_weak(),
+                Rect {
+                    min: Vec2::ZERO,
+                    max: size,
+                },
+                None,
+            ),
+            LoadingPlaceholder::Texture(texture) => {
+                if !images.contains(texture) {
+                    return None;
+                }
+                (
+                    Color::WHITE,
+                    texture.clone_weak(),
+                    Rect {
+                        min: Vec2::ZERO,
+                        max: size,
+                    },
+                    None,
+                )
+            }
+            LoadingPlaceholder::Animation {
+                atlas,
+                frames_per_second,
+            } => {
+                let texture_atlas = texture_atlases.get(atlas)?;
+                if texture_atlas.textures.is_empty() || !images.contains(&texture_atlas.texture) {
+                    return None;
+                }
+                let frame =
+                    (elapsed_seconds * frames_per_second) as usize % texture_atlas.textures.len();
+                let rect = texture_atlas.textures[frame];
+                transform *= Mat4::from_scale((size / rect.size()).extend(1.));
+                (
+                    Color::WHITE,
+                    texture_atlas.texture.clone_weak(),
+                    rect,
+                    Some(texture_atlas.size),
+                )
+            }
+        };
+        Some(ExtractedUiNode {
+            stack_index,
+            transform,
+            background_color,
+            rect,
+            image,
+            atlas_size,
+            clip: clip.map(|clip| clip.clip),
+            scale_factor,
+        })
+    }
+}
@@ -0,0 +1,229 @@
+//! A CPU reference rasterizer for the ui nodes extracted by the exact image systems.
+//!
+//! It draws nodes the way bevy's ui renderer does, without a gpu,
+//! so the output of the extract systems can be compared against golden images in tests.
+//! Textures are sampled with the nearest filter, and a pixel is covered by a quad if its center is.
+
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::path::Path;
+
+use bevy::math::Affine2;
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use bevy::ui::ExtractedUiNode;
+
+/// Set to overwrite golden images with the rasterized output instead of comparing against them
+pub const BLESS_VAR: &str = "EXACT_IMAGE_BLESS";
+
+/// An rgba image in linear color, with premultiplied alpha
+pub struct Raster {
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<Vec4>,
+}
+
+impl Raster {
+    /// A transparent image of the given size in logical pixels
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![Vec4::ZERO; (width * height) as usize],
+        }
+    }
+
+    /// Draws the nodes in the order of their stack indices
+    pub fn rasterize<'a>(
+        width: u32,
+        height: u32,
+        nodes: impl IntoIterator<Item = &'a ExtractedUiNode>,
+        images: &Assets<Image>,
+    ) -> Self {
+        let mut raster = Self::new(width, height);
+        let mut nodes: Vec<_> = nodes.into_iter().collect();
+        nodes.sort_by_key(|node| node.stack_index);
+        for node in nodes {
+            raster.draw(node, images);
+        }
+        raster
+    }
+
+    /// Alpha blends a node over the image.
+    ///
+    /// Nodes with images that aren't in `images` are drawn as solid quads, like bevy's default image.
+    pub fn draw(&mut self, node: &ExtractedUiNode, images: &Assets<Image>) {
+        let rect_size = node.rect.size();
+        let transform = Affine2::from_cols(
+            node.transform.x_axis.truncate().truncate(),
+            node.transform.y_axis.truncate().truncate(),
+            node.transform.w_axis.truncate().truncate(),
+        );
+        if rect_size.cmple(Vec2::ZERO).any() || transform.matrix2.determinant().abs() < 1e-6 {
+            return;
+        }
+        let inverse = transform.inverse();
+        let texture = images.get(&node.image);
+        let extent = node.atlas_size.unwrap_or(node.rect.max);
+        let color = Vec4::from(node.background_color.as_linear_rgba_f32());
+
+        // bounds of the transformed quad, limited to the clip rect and the image
+        let corners = [
+            Vec2::new(-0.5, -0.5),
+            Vec2::new(0.5, -0.5),
+            Vec2::new(-0.5, 0.5),
+            Vec2::new(0.5, 0.5),
+        ]
+        .map(|corner| transform.transform_point2(corner * rect_size));
+        let mut min = corners
+            .iter()
+            .fold(Vec2::splat(f32::MAX), |min, &corner| min.min(corner));
+        let mut max = corners
+            .iter()
+            .fold(Vec2::splat(f32::MIN), |max, &corner| max.max(corner));
+        if let Some(clip) = node.clip {
+            min = min.max(clip.min);
+            max = max.min(clip.max);
+        }
+        let min = min.max(Vec2::ZERO).floor().as_uvec2();
+        let max = max
+            .min(Vec2::new(self.width as f32, self.height as f32))
+            .ceil()
+            .as_uvec2();
+
+        for y in min.y..max.y {
+            for x in min.x..max.x {
+                let center = Vec2::new(x as f32, y as f32) + 0.5;
+                if let Some(clip) = node.clip {
+                    if !(clip.min.cmple(center).all() && center.cmplt(clip.max).all()) {
+                        continue;
+                    }
+                }
+                let local = inverse.transform_point2(center) / rect_size + 0.5;
+                if !(Vec2::ZERO.cmple(local).all() && local.cmplt(Vec2::ONE).all()) {
+                    continue;
+                }
+                let uv = (node.rect.min + local * rect_size) / extent;
+                let texel = texture.map_or(Vec4::ONE, |texture| sample(texture, uv));
+                let source = texel * color;
+                let pixel = &mut self.pixels[(y * self.width + x) as usize];
+                let premultiplied = (source.truncate() * source.w).extend(source.w);
+                *pixel = premultiplied + *pixel * (1. - source.w);
+            }
+        }
+    }
+
+    /// The image as straight alpha rgba bytes in the srgb color space
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|pixel| {
+                let rgb = if 0. < pixel.w {
+                    pixel.truncate() / pixel.w
+                } else {
+                    Vec3::ZERO
+                };
+                let color = Color::rgba_linear(rgb.x, rgb.y, rgb.z, pixel.w).as_rgba_f32();
+                color.map(|channel| (channel.clamp(0., 1.) * 255.).round() as u8)
+            })
+            .collect()
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(file, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.to_rgba8())?;
+        Ok(())
+    }
+
+    /// Panics if any channel of any pixel differs from the golden image by more than `tolerance`.
+    ///
+    /// The golden image is only written, instead of compared, when [`BLESS_VAR`] is set,
+    /// so a missing golden fails the test. On failure the rasterized image is saved
+    /// next to the golden with an `actual.png` extension.
+    #[track_caller]
+    pub fn assert_matches_golden(&self, path: impl AsRef<Path>, tolerance: u8) {
+        let path = path.as_ref();
+        if std::env::var_os(BLESS_VAR).is_some() {
+            self.save_png(path)
+                .unwrap_or_else(|error| panic!("failed to write {}: {error}", path.display()));
+            return;
+        }
+        if !path.exists() {
+            let actual_path = path.with_extension("actual.png");
+            let _ = self.save_png(&actual_path);
+            panic!(
+                "golden missing, set {BLESS_VAR} to write {} (rasterized image saved to {})",
+                path.display(),
+                actual_path.display()
+            );
+        }
+        let (width, height, golden) = load_png(path)
+            .unwrap_or_else(|error| panic!("failed to read {}: {error}", path.display()));
+        let actual = self.to_rgba8();
+        let mismatched = if (width, height) == (self.width, self.height) {
+            actual
+                .chunks(4)
+                .zip(golden.chunks(4))
+                .filter(|(actual, golden)| {
+                    actual
+                        .iter()
+                        .zip(golden.iter())
+                        .any(|(a, g)| a.abs_diff(*g) > tolerance)
+                })
+                .count()
+        } else {
+            actual.len() / 4
+        };
+        if mismatched != 0 {
+            let actual_path = path.with_extension("actual.png");
+            let _ = self.save_png(&actual_path);
+            panic!(
+                "{mismatched} pixels differ from {}, the rasterized image was saved to {}. \
+                Set {BLESS_VAR} to update the golden image",
+                path.display(),
+                actual_path.display()
+            );
+        }
+    }
+}
+
+/// Samples the texel at the given texture coordinates with the nearest filter, in linear color
+fn sample(texture: &Image, uv: Vec2) -> Vec4 {
+    let size = texture.size().as_uvec2();
+    let texel = (uv * size.as_vec2())
+        .floor()
+        .as_uvec2()
+        .min(size.max(UVec2::ONE) - UVec2::ONE);
+    let offset = 4 * (texel.y * size.x + texel.x) as usize;
+    let Some(&[r, g, b, a]) = texture.data.get(offset..offset + 4) else {
+        return Vec4::ONE;
+    };
+    let [r, g, b, a] = [r, g, b, a].map(|channel| channel as f32 / 255.);
+    match texture.texture_descriptor.format {
+        TextureFormat::Rgba8UnormSrgb => Vec4::from(Color::rgba(r, g, b, a).as_linear_rgba_f32()),
+        TextureFormat::Rgba8Unorm => Vec4::new(r, g, b, a),
+        format => panic!("the rasterizer can't sample {format:?} textures"),
+    }
+}
+
+fn load_png(path: &Path) -> io::Result<(u32, u32, Vec<u8>)> {
+    let mut decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+    decoder.set_transformations(png::Transformations::EXPAND);
+    let mut reader = decoder.read_info()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer)?;
+    if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "golden images must be 8 bit rgba",
+        ));
+    }
+    buffer.truncate(info.buffer_size());
+    Ok((info.width, info.height, buffer))
+}
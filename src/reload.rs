@@ -0,0 +1,161 @@
+use bevy::asset::HandleId;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy::utils::HashSet;
+
+use crate::descriptor::ExactImageDescriptor;
+use crate::import::ImportedAtlas;
+use crate::layers::ExactImageLayers;
+use crate::layers::LayerImage;
+use crate::source::ExactSourceImage;
+
+/// The entities whose images reference each asset, so that a modified asset only marks its own images
+#[derive(Default)]
+pub struct ReloadIndex {
+    entities: HashMap<HandleId, HashSet<Entity>>,
+    assets: HashMap<Entity, Vec<HandleId>>,
+}
+
+impl ReloadIndex {
+    fn insert(&mut self, entity: Entity, assets: Vec<HandleId>) {
+        self.remove(entity);
+        for &id in &assets {
+            self.entities.entry(id).or_default().insert(entity);
+        }
+        self.assets.insert(entity, assets);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        for id in self.assets.remove(&entity).into_iter().flatten() {
+            if let Some(entities) = self.entities.get_mut(&id) {
+                entities.remove(&entity);
+                if entities.is_empty() {
+                    self.entities.remove(&id);
+                }
+            }
+        }
+    }
+
+    /// The entities referencing any of the assets
+    fn referencing(&self, assets: &HashSet<HandleId>) -> HashSet<Entity> {
+        assets
+            .iter()
+            .filter_map(|id| self.entities.get(id))
+            .flatten()
+            .copied()
+            .collect()
+    }
+}
+
+/// Marks the exact images whose textures, atlases or descriptors were modified as changed,
+/// so hot reloaded assets are resized and their atlas indices checked again.
+///
+/// The layout systems only update images that have changed, so this also keeps
+/// images whose textures are mutated at runtime the right size.
+/// The assets referenced by each image are indexed as the image changes,
+/// so only the images of modified assets are visited.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn mark_modified_exact_images(
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut atlas_events: EventReader<AssetEvent<TextureAtlas>>,
+    mut descriptor_events: EventReader<AssetEvent<ExactImageDescriptor>>,
+    mut imported_atlas_events: EventReader<AssetEvent<ImportedAtlas>>,
+    atlases: Res<Assets<TextureAtlas>>,
+    descriptors: Res<Assets<ExactImageDescriptor>>,
+    mut source_index: Local<ReloadIndex>,
+    mut layer_index: Local<ReloadIndex>,
+    mut source_images: ParamSet<(
+        Query<(Entity, &ExactSourceImage), Changed<ExactSourceImage>>,
+        Query<&mut ExactSourceImage>,
+    )>,
+    mut layers: ParamSet<(
+        Query<(Entity, &ExactImageLayers), Changed<ExactImageLayers>>,
+        Query<&mut ExactImageLayers>,
+    )>,
+    removed_source_images: RemovedComponents<ExactSourceImage>,
+    removed_layers: RemovedComponents<ExactImageLayers>,
+) {
+    for entity in removed_source_images.iter() {
+        source_index.remove(entity);
+    }
+    for entity in removed_layers.iter() {
+        layer_index.remove(entity);
+    }
+    for (entity, source_image) in source_images.p0().iter() {
+        let assets = std::iter::once(source_image.source.id())
+            .chain(source_image.frames.as_ref().map(|frames| frames.id()))
+            .collect();
+        source_index.insert(entity, assets);
+    }
+    for (entity, image_layers) in layers.p0().iter() {
+        let assets = image_layers
+            .layers
+            .iter()
+            .map(|layer| match &layer.image {
+                LayerImage::Texture(texture) => texture.id(),
+                LayerImage::Atlas { atlas, .. } => atlas.id(),
+            })
+            .collect();
+        layer_index.insert(entity, assets);
+    }
+
+    let modified_textures: HashSet<Handle<Image>> = image_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { handle } => Some(handle.clone_weak()),
+            _ => None,
+        })
+        .collect();
+    let mut modified: HashSet<HandleId> = modified_textures.iter().map(Handle::id).collect();
+    modified.extend(atlas_events.iter().filter_map(|event| match event {
+        AssetEvent::Modified { handle } => Some(handle.id()),
+        _ => None,
+    }));
+    modified.extend(descriptor_events.iter().filter_map(|event| match event {
+        AssetEvent::Modified { handle } => Some(handle.id()),
+        _ => None,
+    }));
+    modified.extend(
+        imported_atlas_events
+            .iter()
+            .filter_map(|event| match event {
+                AssetEvent::Modified { handle } => Some(handle.id()),
+                _ => None,
+            }),
+    );
+    if modified.is_empty() {
+        return;
+    }
+
+    // atlases and descriptors are modified along with their textures
+    if !modified_textures.is_empty() {
+        modified.extend(
+            atlases
+                .iter()
+                .filter(|(_, atlas)| modified_textures.contains(&atlas.texture))
+                .map(|(id, _)| id),
+        );
+        modified.extend(
+            descriptors
+                .iter()
+                .filter(|(_, descriptor)| modified_textures.contains(&descriptor.texture))
+                .map(|(id, _)| id),
+        );
+    }
+
+    let mut source_images = source_images.p1();
+    for entity in source_index.referencing(&modified) {
+        match source_images.get_mut(entity) {
+            Ok(mut source_image) => source_image.set_changed(),
+            // removed after this system ran in an earlier frame
+            Err(_) => source_index.remove(entity),
+        }
+    }
+    let mut layers = layers.p1();
+    for entity in layer_index.referencing(&modified) {
+        match layers.get_mut(entity) {
+            Ok(mut image_layers) => image_layers.set_changed(),
+            Err(_) => layer_index.remove(entity),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+struct View {
+    view_proj: mat4x4<f32>,
+    inverse_view_proj: mat4x4<f32>,
+    view: mat4x4<f32>,
+    inverse_view: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    inverse_projection: mat4x4<f32>,
+    world_position: vec3<f32>,
+    // viewport(x_origin, y_origin, width, height)
+    viewport: vec4<f32>,
+};
+@group(0) @binding(0)
+var<uniform> view: View;
+
+let MODE_IMAGE: u32 = 0u;
+let MODE_OUTLINE: u32 = 1u;
+let MODE_SHADOW: u32 = 2u;
+
+struct VertexOutput {
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    // grayscale, brightness, contrast, saturation
+    @location(2) effects: vec4<f32>,
+    @location(3) hue_rotation: f32,
+    @location(4) uv_bounds: vec4<f32>,
+    @location(5) radius: vec2<f32>,
+    @location(6) @interpolate(flat) mode: u32,
+    @builtin(position) position: vec4<f32>,
+};
+
+@vertex
+fn vertex(
+    @location(0) vertex_position: vec3<f32>,
+    @location(1) vertex_uv: vec2<f32>,
+    @location(2) vertex_color: vec4<f32>,
+    @location(3) vertex_effects: vec4<f32>,
+    @location(4) vertex_hue_rotation: f32,
+    @location(5) vertex_uv_bounds: vec4<f32>,
+    @location(6) vertex_radius: vec2<f32>,
+    @location(7) vertex_mode: u32,
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.uv = vertex_uv;
+    out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
+    out.color = vertex_color;
+    out.effects = vertex_effects;
+    out.hue_rotation = vertex_hue_rotation;
+    out.uv_bounds = vertex_uv_bounds;
+    out.radius = vertex_radius;
+    out.mode = vertex_mode;
+    return out;
+}
+
+@group(1) @binding(0)
+var image_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var image_sampler: sampler;
+
+fn luminance(rgb: vec3<f32>) -> f32 {
+    return dot(rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+// rotates the color around the gray axis of the rgb cube
+fn rotate_hue(rgb: vec3<f32>, angle: f32) -> vec3<f32> {
+    let k = vec3<f32>(0.57735);
+    let c = cos(angle);
+    return rgb * c + cross(k, rgb) * sin(angle) + k * dot(k, rgb) * (1.0 - c);
+}
+
+// colors brighter than white are kept, for hdr and over-bright tints
+fn apply_effects(rgb: vec3<f32>, effects: vec4<f32>, hue_rotation: f32) -> vec3<f32> {
+    // grayscale 0, brightness 1, contrast 1 and saturation 1 leave the color unchanged
+    if (all(effects == vec4<f32>(0.0, 1.0, 1.0, 1.0)) && hue_rotation == 0.0) {
+        return rgb;
+    }
+    // rotating the hue and saturating can push channels below zero
+    var out = max(rotate_hue(rgb, hue_rotation), vec3<f32>(0.0));
+    out = max(mix(vec3<f32>(luminance(out)), out, effects.w), vec3<f32>(0.0));
+    out = mix(out, vec3<f32>(luminance(out)), effects.x);
+    // and so can raising the contrast of dark colors
+    out = max((out - 0.5) * effects.z + 0.5, vec3<f32>(0.0));
+    return out * effects.y;
+}
+
+// alpha of the image at uv, transparent outside of the image's texture rect
+fn sample_alpha(uv: vec2<f32>, uv_bounds: vec4<f32>) -> f32 {
+    let inside = step(uv_bounds.xy, uv) * step(uv, uv_bounds.zw);
+    return textureSampleLevel(image_texture, image_sampler, uv, 0.0).a * inside.x * inside.y;
+}
+
+// the image's alpha dilated by radius, for outlines
+fn dilated_alpha(uv: vec2<f32>, uv_bounds: vec4<f32>, radius: vec2<f32>) -> f32 {
+    var alpha = sample_alpha(uv, uv_bounds);
+    for (var i = 0; i < 16; i = i + 1) {
+        let angle = f32(i) * 0.39269908;
+        let direction = vec2<f32>(cos(angle), sin(angle));
+        alpha = max(alpha, sample_alpha(uv + direction * radius, uv_bounds));
+        alpha = max(alpha, sample_alpha(uv + 0.5 * direction * radius, uv_bounds));
+    }
+    return alpha;
+}
+
+// the image's alpha averaged over a disc of the given radius, for soft shadows
+fn blurred_alpha(uv: vec2<f32>, uv_bounds: vec4<f32>, radius: vec2<f32>) -> f32 {
+    var total = sample_alpha(uv, uv_bounds);
+    var weight = 1.0;
+    for (var ring = 1; ring <= 3; ring = ring + 1) {
+        let distance = f32(ring) / 3.0;
+        let ring_weight = 1.0 - 0.5 * distance;
+        for (var i = 0; i < 8; i = i + 1) {
+            let angle = (f32(i) + 0.5 * f32(ring)) * 0.78539816;
+            let offset = vec2<f32>(cos(angle), sin(angle)) * distance * radius;
+            total = total + ring_weight * sample_alpha(uv + offset, uv_bounds);
+            weight = weight + ring_weight;
+        }
+    }
+    return total / weight;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    var color = textureSample(image_texture, image_sampler, in.uv);
+    if (in.mode == MODE_OUTLINE) {
+        color = vec4<f32>(in.color.rgb, in.color.a * dilated_alpha(in.uv, in.uv_bounds, in.radius));
+    } else if (in.mode == MODE_SHADOW) {
+        color = vec4<f32>(in.color.rgb, in.color.a * blurred_alpha(in.uv, in.uv_bounds, in.radius));
+    } else {
+        color = in.color * color;
+#ifdef PREMULTIPLIED_TEXTURE
+        // the effects adjust straight colors, and fully transparent texels have none to adjust
+        if (color.a > 0.0) {
+            let rgb = apply_effects(color.rgb / color.a, in.effects, in.hue_rotation);
+            color = vec4<f32>(rgb * color.a, color.a);
+        }
+#else
+        color = vec4<f32>(apply_effects(color.rgb, in.effects, in.hue_rotation), color.a);
+#endif
+    }
+#ifdef BLEND_PREMULTIPLY_ALPHA
+    color = vec4<f32>(color.rgb * color.a, color.a);
+#endif
+#ifdef BLEND_MULTIPLY
+    color = vec4<f32>(mix(vec3<f32>(1.0), color.rgb, color.a), color.a);
+#endif
+    return color;
+}
@@ -0,0 +1,698 @@
+mod pipeline;
+
+pub use pipeline::*;
+
+use std::ops::Range;
+
+use bevy::asset::load_internal_asset;
+use bevy::asset::HandleUntyped;
+use bevy::ecs::system::lifetimeless::Read;
+use bevy::ecs::system::lifetimeless::SQuery;
+use bevy::ecs::system::lifetimeless::SRes;
+use bevy::ecs::system::SystemParamItem;
+use bevy::math::Vec4Swizzles;
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_phase::AddRenderCommand;
+use bevy::render::render_phase::DrawFunctions;
+use bevy::render::render_phase::EntityRenderCommand;
+use bevy::render::render_phase::RenderCommandResult;
+use bevy::render::render_phase::RenderPhase;
+use bevy::render::render_phase::SetItemPipeline;
+use bevy::render::render_phase::TrackedRenderPass;
+use bevy::render::render_resource::*;
+use bevy::render::renderer::RenderDevice;
+use bevy::render::renderer::RenderQueue;
+use bevy::render::texture::ImageSampler;
+use bevy::render::view::ExtractedView;
+use bevy::render::RenderApp;
+use bevy::render::RenderStage;
+use bevy::sprite::SpriteAssetEvents;
+use bevy::ui::ExtractedUiNode;
+use bevy::ui::ExtractedUiNodes;
+use bevy::ui::SetUiViewBindGroup;
+use bevy::ui::TransparentUi;
+use bevy::utils::FloatOrd;
+use bevy::utils::HashMap;
+use bytemuck::Pod;
+use bytemuck::Zeroable;
+
+use crate::ImageBlendMode;
+use crate::ImageEffects;
+use crate::ImageOutline;
+use crate::ImageSampling;
+use crate::ImageShadow;
+
+pub const EXACT_IMAGE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4389152609827461131);
+
+/// Image of the empty nodes that mark where exact images are drawn among bevy's ui nodes.
+///
+/// The image is never loaded, so bevy's ui renderer starts a new batch at each marker and culls it.
+pub const EXACT_IMAGE_MARKER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 7021534498364712203);
+
+pub(crate) fn build_exact_image_render(app: &mut App) {
+    load_internal_asset!(
+        app,
+        EXACT_IMAGE_SHADER_HANDLE,
+        "exact_image.wgsl",
+        Shader::from_wgsl
+    );
+
+    let render_app = match app.get_sub_app_mut(RenderApp) {
+        Ok(render_app) => render_app,
+        Err(_) => return,
+    };
+
+    render_app
+        .init_resource::<ExtractedExactImages>()
+        .add_system_to_stage(RenderStage::Extract, order_extracted_ui_nodes.at_end());
+
+    // headless apps can run extraction without a gpu, but can't draw anything
+    if !render_app.world.contains_resource::<RenderDevice>() {
+        return;
+    }
+
+    render_app
+        .init_resource::<ExactImagePipeline>()
+        .init_resource::<SpecializedRenderPipelines<ExactImagePipeline>>()
+        .init_resource::<ExactImageBindGroups>()
+        .init_resource::<ExactImageSamplers>()
+        .init_resource::<ExactImageMeta>()
+        .add_render_command::<TransparentUi, DrawExactImage>()
+        .add_system_to_stage(RenderStage::Prepare, prepare_exact_images)
+        .add_system_to_stage(RenderStage::Queue, queue_exact_images);
+}
+
+/// An exact image that can't be drawn by bevy's ui renderer
+pub struct ExtractedExactImage {
+    pub node: ExtractedUiNode,
+    pub blend_mode: ImageBlendMode,
+    pub effects: ImageEffects,
+    /// Outline with its width multiplied by the ui scale
+    pub outline: Option<ImageOutline>,
+    /// Shadow with its offset and blur radius multiplied by the ui scale
+    pub shadow: Option<ImageShadow>,
+    /// Sampler used instead of the texture's own
+    pub sampling: Option<ImageSampling>,
+    /// Depth of the image among the ui nodes, set by [`order_extracted_ui_nodes`]
+    pub z: f32,
+    /// Depth of the image's outline and shadow, between the image and the node drawn before it
+    pub effects_z: f32,
+    /// Position of the image's marker among the extracted ui nodes, set by [`order_extracted_ui_nodes`]
+    pub order: usize,
+}
+
+impl ExtractedExactImage {
+    /// Only alpha blended images without any effects can be drawn by bevy's ui renderer
+    fn is_plain(&self) -> bool {
+        self.blend_mode == ImageBlendMode::Alpha
+            && self.effects == ImageEffects::default()
+            && self.outline.is_none()
+            && self.shadow.is_none()
+            && self.sampling.is_none()
+    }
+}
+
+/// Exact images extracted this frame that are drawn by the exact image renderer
+/// instead of being batched with the default `ExtractedUiNodes`
+#[derive(Resource, Default)]
+pub struct ExtractedExactImages {
+    pub images: Vec<ExtractedExactImage>,
+}
+
+impl ExtractedExactImages {
+    /// Plain images are drawn by bevy's ui renderer,
+    /// everything else is drawn by the exact image renderer.
+    pub fn push_or_forward(
+        &mut self,
+        extracted_uinodes: &mut ExtractedUiNodes,
+        image: ExtractedExactImage,
+    ) {
+        if image.is_plain() {
+            extracted_uinodes.uinodes.push(image.node);
+        } else {
+            // an empty node in the image's place splits bevy's batches around it
+            extracted_uinodes.uinodes.push(ExtractedUiNode {
+                stack_index: image.node.stack_index,
+                transform: Mat4::IDENTITY,
+                background_color: Color::NONE,
+                rect: Rect::default(),
+                image: EXACT_IMAGE_MARKER_HANDLE.typed_weak(),
+                atlas_size: None,
+                clip: None,
+                scale_factor: image.node.scale_factor,
+            });
+            self.images.push(image);
+        }
+    }
+}
+
+/// Orders bevy's ui nodes and the exact images between them by ui stack index.
+///
+/// Bevy sorts its ui batches by the depth of their last node, so every node is given a depth from
+/// its place in the ui stack, and every exact image the depth of the marker it left among the nodes.
+pub fn order_extracted_ui_nodes(
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    mut extracted_images: ResMut<ExtractedExactImages>,
+) {
+    // both sorts are stable, so an image's marker keeps its place among the markers
+    let uinodes = &mut extracted_uinodes.uinodes;
+    uinodes.sort_by_key(|node| node.stack_index);
+    extracted_images
+        .images
+        .sort_by_key(|image| image.node.stack_index);
+
+    let marker = EXACT_IMAGE_MARKER_HANDLE.typed_weak::<Image>();
+    let count = uinodes.len() as f32;
+    let mut images = extracted_images.images.iter_mut();
+    for (order, node) in uinodes.iter_mut().enumerate() {
+        let z = order as f32 / count;
+        node.transform.w_axis.z = z;
+        if node.image == marker {
+            if let Some(image) = images.next() {
+                image.z = z;
+                image.effects_z = (order as f32 - 0.5) / count;
+                image.order = order;
+            }
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+pub struct ExactImageVertex {
+    pub position: [f32; 3],
+    pub uv: [f32; 2],
+    pub color: [f32; 4],
+    /// grayscale, brightness, contrast and saturation
+    pub effects: [f32; 4],
+    pub hue_rotation: f32,
+    /// the uvs of the image's texture rect as min x, min y, max x, max y
+    pub uv_bounds: [f32; 4],
+    /// outline width or shadow blur radius in uv units
+    pub radius: [f32; 2],
+    /// the [`QuadMode`] of the quad
+    pub mode: u32,
+}
+
+#[derive(Resource)]
+pub struct ExactImageMeta {
+    vertices: BufferVec<ExactImageVertex>,
+}
+
+impl Default for ExactImageMeta {
+    fn default() -> Self {
+        Self {
+            vertices: BufferVec::new(BufferUsages::VERTEX),
+        }
+    }
+}
+
+const QUAD_VERTEX_POSITIONS: [Vec3; 4] = [
+    Vec3::new(-0.5, -0.5, 0.0),
+    Vec3::new(0.5, -0.5, 0.0),
+    Vec3::new(0.5, 0.5, 0.0),
+    Vec3::new(-0.5, 0.5, 0.0),
+];
+
+const QUAD_INDICES: [usize; 6] = [0, 2, 3, 0, 1, 2];
+
+#[derive(Component)]
+pub struct ExactImageBatch {
+    pub range: Range<u32>,
+    pub image: Handle<Image>,
+    pub blend_mode: ImageBlendMode,
+    pub sampling: Option<ImageSampling>,
+    /// Depth of the last image in the batch, sorted with the depths of bevy's ui batches
+    pub z: f32,
+}
+
+impl ExactImageBatch {
+    /// Key of the batch's bind group in [`ExactImageBindGroups`]
+    pub fn bind_group_key(&self) -> (Handle<Image>, Option<ImageSampling>) {
+        (self.image.clone_weak(), self.sampling)
+    }
+}
+
+/// What a quad of an exact image draws, must match the `MODE_` constants in the shader
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(u32)]
+pub enum QuadMode {
+    Image = 0,
+    Outline = 1,
+    Shadow = 2,
+}
+
+struct Quad {
+    transform: Mat4,
+    /// linear rgba
+    color: [f32; 4],
+    mode: QuadMode,
+    /// How far the quad extends past the edges of the image in logical pixels,
+    /// leaving room for an outline or a blurred shadow
+    margin: f32,
+}
+
+/// Push the vertices of a quad covering the image, returns false if the quad was completely clipped
+fn push_quad(
+    vertices: &mut Vec<ExactImageVertex>,
+    node: &ExtractedUiNode,
+    effects: &ImageEffects,
+    quad: Quad,
+) -> bool {
+    let atlas_extent = node.atlas_size.unwrap_or(node.rect.max);
+    let local_margin = if quad.margin == 0. {
+        Vec2::ZERO
+    } else {
+        let scale = Vec2::new(
+            quad.transform.x_axis.truncate().length(),
+            quad.transform.y_axis.truncate().length(),
+        );
+        quad.margin / scale
+    };
+    let rect = Rect {
+        min: node.rect.min - local_margin,
+        max: node.rect.max + local_margin,
+    };
+    let rect_size = rect.size().extend(1.0);
+
+    // Specify the corners of the node
+    let positions =
+        QUAD_VERTEX_POSITIONS.map(|pos| (quad.transform * (pos * rect_size).extend(1.)).xyz());
+
+    // Calculate the effect of clipping
+    // Note: this won't work with rotation/scaling, but that's much more complex (may need more that 2 quads)
+    let positions_diff = if let Some(clip) = node.clip {
+        [
+            Vec2::new(
+                f32::max(clip.min.x - positions[0].x, 0.),
+                f32::max(clip.min.y - positions[0].y, 0.),
+            ),
+            Vec2::new(
+                f32::min(clip.max.x - positions[1].x, 0.),
+                f32::max(clip.min.y - positions[1].y, 0.),
+            ),
+            Vec2::new(
+                f32::min(clip.max.x - positions[2].x, 0.),
+                f32::min(clip.max.y - positions[2].y, 0.),
+            ),
+            Vec2::new(
+                f32::max(clip.min.x - positions[3].x, 0.),
+                f32::min(clip.max.y - positions[3].y, 0.),
+            ),
+        ]
+    } else {
+        [Vec2::ZERO; 4]
+    };
+
+    let positions_clipped = [
+        positions[0] + positions_diff[0].extend(0.),
+        positions[1] + positions_diff[1].extend(0.),
+        positions[2] + positions_diff[2].extend(0.),
+        positions[3] + positions_diff[3].extend(0.),
+    ];
+
+    let transformed_rect_size = quad.transform.transform_vector3(rect_size);
+
+    // Don't try to cull nodes that have a rotation
+    if quad.transform.x_axis[1] == 0.0 {
+        // Cull nodes that are completely clipped
+        if positions_diff[0].x - positions_diff[1].x >= transformed_rect_size.x
+            || positions_diff[1].y - positions_diff[2].y >= transformed_rect_size.y
+        {
+            return false;
+        }
+    }
+
+    let uvs = [
+        Vec2::new(
+            rect.min.x + positions_diff[0].x * node.scale_factor,
+            rect.min.y + positions_diff[0].y * node.scale_factor,
+        ),
+        Vec2::new(
+            rect.max.x + positions_diff[1].x * node.scale_factor,
+            rect.min.y + positions_diff[1].y * node.scale_factor,
+        ),
+        Vec2::new(
+            rect.max.x + positions_diff[2].x * node.scale_factor,
+            rect.max.y + positions_diff[2].y * node.scale_factor,
+        ),
+        Vec2::new(
+            rect.min.x + positions_diff[3].x * node.scale_factor,
+            rect.max.y + positions_diff[3].y * node.scale_factor,
+        ),
+    ]
+    .map(|pos| pos / atlas_extent);
+
+    // the sub-rect of the texture covered by the image, anything outside is transparent
+    let uv_min = node.rect.min / atlas_extent;
+    let uv_max = node.rect.max / atlas_extent;
+    let uv_bounds = [uv_min.x, uv_min.y, uv_max.x, uv_max.y];
+    let radius = (local_margin / atlas_extent).into();
+
+    let color = quad.color;
+    let image_effects = [
+        effects.grayscale,
+        effects.brightness,
+        effects.contrast,
+        effects.saturation,
+    ];
+    for i in QUAD_INDICES {
+        vertices.push(ExactImageVertex {
+            position: positions_clipped[i].into(),
+            uv: uvs[i].into(),
+            color,
+            effects: image_effects,
+            hue_rotation: effects.hue_rotation,
+            uv_bounds,
+            radius,
+            mode: quad.mode as u32,
+        });
+    }
+    true
+}
+
+/// Vertices of the exact images extracted in a frame, and the batches they are drawn in
+#[derive(Default)]
+pub struct BatchedExactImages {
+    pub vertices: Vec<ExactImageVertex>,
+    pub batches: Vec<ExactImageBatch>,
+}
+
+/// Builds the vertices of the images, which must be in the order given by [`order_extracted_ui_nodes`],
+/// and batches consecutive images drawn with the same texture, blend mode and sampler.
+pub fn batch_exact_images(
+    images: impl IntoIterator<Item = ExtractedExactImage>,
+) -> BatchedExactImages {
+    let mut vertices = Vec::new();
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut end = 0;
+    let mut current_batch: Option<ExactImageBatch> = None;
+    let mut last_order = None;
+    for ExtractedExactImage {
+        node,
+        blend_mode,
+        effects,
+        outline,
+        shadow,
+        sampling,
+        z,
+        effects_z,
+        order,
+    } in images
+    {
+        // shadows and outlines are drawn beneath the image
+        let mut effect_quads = Vec::with_capacity(2);
+        if let Some(shadow) = shadow {
+            effect_quads.push(Quad {
+                transform: Mat4::from_translation(shadow.offset.extend(0.)) * node.transform,
+                color: shadow.color.as_linear_rgba_f32(),
+                mode: QuadMode::Shadow,
+                margin: shadow.blur_radius,
+            });
+        }
+        if let Some(outline) = outline {
+            effect_quads.push(Quad {
+                transform: node.transform,
+                color: outline.color.as_linear_rgba_f32(),
+                mode: QuadMode::Outline,
+                margin: outline.width,
+            });
+        }
+
+        // images can only be batched if no other ui node is drawn between them
+        let batch_changed = match &current_batch {
+            Some(batch) => {
+                batch.image != node.image
+                    || batch.blend_mode != blend_mode
+                    || batch.sampling != sampling
+                    || last_order.map(|last: usize| last + 1) != Some(order)
+                    || !effect_quads.is_empty()
+            }
+            None => true,
+        };
+        last_order = Some(order);
+        if batch_changed {
+            if let Some(batch) = current_batch.take() {
+                if start != end {
+                    batches.push(ExactImageBatch {
+                        range: start..end,
+                        ..batch
+                    });
+                    start = end;
+                }
+            }
+            // the shadow and outline are alpha blended whatever the blend mode of the image,
+            // in a batch of their own
+            for quad in effect_quads {
+                if push_quad(&mut vertices, &node, &effects, quad) {
+                    end += QUAD_INDICES.len() as u32;
+                }
+            }
+            if start != end {
+                batches.push(ExactImageBatch {
+                    range: start..end,
+                    image: node.image.clone_weak(),
+                    blend_mode: ImageBlendMode::Alpha,
+                    sampling,
+                    z: effects_z,
+                });
+                start = end;
+            }
+            current_batch = Some(ExactImageBatch {
+                range: 0..0,
+                image: node.image.clone_weak(),
+                blend_mode,
+                sampling,
+                z,
+            });
+        }
+        if let Some(batch) = &mut current_batch {
+            batch.z = z;
+        }
+
+        let mut color = node.background_color.as_linear_rgba_f32();
+        if blend_mode == ImageBlendMode::PremultipliedAlpha {
+            // the colors of the texture are multiplied by their alpha, so the tint's must be as well
+            let alpha = color[3];
+            for channel in &mut color[..3] {
+                *channel *= alpha;
+            }
+        }
+        let quad = Quad {
+            transform: node.transform,
+            color,
+            mode: QuadMode::Image,
+            margin: 0.,
+        };
+        if push_quad(&mut vertices, &node, &effects, quad) {
+            end += QUAD_INDICES.len() as u32;
+        }
+    }
+
+    // if start != end, there is one last batch to process
+    if let Some(batch) = current_batch {
+        if start != end {
+            batches.push(ExactImageBatch {
+                range: start..end,
+                ..batch
+            });
+        }
+    }
+    BatchedExactImages { vertices, batches }
+}
+
+pub fn prepare_exact_images(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut meta: ResMut<ExactImageMeta>,
+    mut extracted_images: ResMut<ExtractedExactImages>,
+) {
+    meta.vertices.clear();
+    let BatchedExactImages { vertices, batches } =
+        batch_exact_images(extracted_images.images.drain(..));
+    for vertex in vertices {
+        meta.vertices.push(vertex);
+    }
+    commands.spawn_batch(batches);
+    meta.vertices.write_buffer(&render_device, &render_queue);
+}
+
+/// Bind groups of each texture, and of each sampler it is drawn with
+#[derive(Resource, Default)]
+pub struct ExactImageBindGroups {
+    pub values: HashMap<(Handle<Image>, Option<ImageSampling>), BindGroup>,
+}
+
+/// Samplers of images with an [`ImageSampling`] override
+#[derive(Resource)]
+pub struct ExactImageSamplers {
+    pub nearest: Sampler,
+    pub linear: Sampler,
+    pub linear_mipmapped: Sampler,
+}
+
+impl ImageSampling {
+    /// Descriptor of the sampler the texture is drawn with
+    pub fn sampler_descriptor(self) -> SamplerDescriptor<'static> {
+        match self {
+            ImageSampling::Nearest => ImageSampler::nearest_descriptor(),
+            ImageSampling::Linear => SamplerDescriptor {
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                // always sample the full size texture
+                lod_max_clamp: 0.,
+                ..Default::default()
+            },
+            ImageSampling::LinearMipmapped => ImageSampler::linear_descriptor(),
+        }
+    }
+}
+
+impl FromWorld for ExactImageSamplers {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let create =
+            |sampling: ImageSampling| render_device.create_sampler(&sampling.sampler_descriptor());
+        ExactImageSamplers {
+            nearest: create(ImageSampling::Nearest),
+            linear: create(ImageSampling::Linear),
+            linear_mipmapped: create(ImageSampling::LinearMipmapped),
+        }
+    }
+}
+
+impl ExactImageSamplers {
+    fn get(&self, sampling: ImageSampling) -> &Sampler {
+        match sampling {
+            ImageSampling::Nearest => &self.nearest,
+            ImageSampling::Linear => &self.linear,
+            ImageSampling::LinearMipmapped => &self.linear_mipmapped,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_exact_images(
+    draw_functions: Res<DrawFunctions<TransparentUi>>,
+    render_device: Res<RenderDevice>,
+    exact_image_pipeline: Res<ExactImagePipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<ExactImagePipeline>>,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    mut image_bind_groups: ResMut<ExactImageBindGroups>,
+    samplers: Res<ExactImageSamplers>,
+    gpu_images: Res<RenderAssets<Image>>,
+    batches: Query<(Entity, &ExactImageBatch)>,
+    mut views: Query<(&ExtractedView, &mut RenderPhase<TransparentUi>)>,
+    events: Res<SpriteAssetEvents>,
+) {
+    // If an image has changed, the GpuImage has (probably) changed
+    for event in &events.images {
+        match event {
+            AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
+                image_bind_groups
+                    .values
+                    .retain(|(image, _), _| image != handle);
+            }
+            AssetEvent::Created { .. } => {}
+        }
+    }
+
+    let draw_exact_image_function = draw_functions.read().get_id::<DrawExactImage>().unwrap();
+    for (view, mut transparent_phase) in &mut views {
+        for (entity, batch) in &batches {
+            let Some(gpu_image) = gpu_images.get(&batch.image) else {
+                continue;
+            };
+            image_bind_groups
+                .values
+                .entry(batch.bind_group_key())
+                .or_insert_with(|| {
+                    let sampler = batch
+                        .sampling
+                        .map_or(&gpu_image.sampler, |sampling| samplers.get(sampling));
+                    render_device.create_bind_group(&BindGroupDescriptor {
+                        entries: &[
+                            BindGroupEntry {
+                                binding: 0,
+                                resource: BindingResource::TextureView(&gpu_image.texture_view),
+                            },
+                            BindGroupEntry {
+                                binding: 1,
+                                resource: BindingResource::Sampler(sampler),
+                            },
+                        ],
+                        label: Some("exact_image_bind_group"),
+                        layout: &exact_image_pipeline.image_layout,
+                    })
+                });
+            let pipeline = pipelines.specialize(
+                &mut pipeline_cache,
+                &exact_image_pipeline,
+                ExactImagePipelineKey {
+                    hdr: view.hdr,
+                    blend_mode: batch.blend_mode,
+                },
+            );
+            transparent_phase.add(TransparentUi {
+                draw_function: draw_exact_image_function,
+                pipeline,
+                entity,
+                sort_key: FloatOrd(batch.z),
+            });
+        }
+    }
+}
+
+pub type DrawExactImage = (
+    SetItemPipeline,
+    SetUiViewBindGroup<0>,
+    SetExactImageTextureBindGroup<1>,
+    DrawExactImageBatch,
+);
+
+pub struct SetExactImageTextureBindGroup<const I: usize>;
+impl<const I: usize> EntityRenderCommand for SetExactImageTextureBindGroup<I> {
+    type Param = (SRes<ExactImageBindGroups>, SQuery<Read<ExactImageBatch>>);
+
+    fn render<'w>(
+        _view: Entity,
+        item: Entity,
+        (image_bind_groups, query_batch): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let batch = query_batch.get(item).unwrap();
+        let image_bind_groups = image_bind_groups.into_inner();
+
+        match image_bind_groups.values.get(&batch.bind_group_key()) {
+            Some(bind_group) => {
+                pass.set_bind_group(I, bind_group, &[]);
+                RenderCommandResult::Success
+            }
+            None => RenderCommandResult::Failure,
+        }
+    }
+}
+
+pub struct DrawExactImageBatch;
+impl EntityRenderCommand for DrawExactImageBatch {
+    type Param = (SRes<ExactImageMeta>, SQuery<Read<ExactImageBatch>>);
+
+    fn render<'w>(
+        _view: Entity,
+        item: Entity,
+        (meta, query_batch): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let batch = query_batch.get(item).unwrap();
+
+        pass.set_vertex_buffer(0, meta.into_inner().vertices.buffer().unwrap().slice(..));
+        pass.draw(batch.range.clone(), 0..1);
+        RenderCommandResult::Success
+    }
+}
@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::*;
+use bevy::render::texture::BevyDefault;
+use bevy::render::view::ViewTarget;
+use bevy::ui::UiPipeline;
+
+use crate::ImageBlendMode;
+
+#[derive(Resource)]
+pub struct ExactImagePipeline {
+    pub view_layout: BindGroupLayout,
+    pub image_layout: BindGroupLayout,
+}
+
+impl FromWorld for ExactImagePipeline {
+    fn from_world(world: &mut World) -> Self {
+        // Share bevy's ui layouts so that the view bind group created by bevy's ui renderer
+        // can be bound by the exact image draw function as well.
+        let ui_pipeline = world.resource::<UiPipeline>();
+        ExactImagePipeline {
+            view_layout: ui_pipeline.view_layout.clone(),
+            image_layout: ui_pipeline.image_layout.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ExactImagePipelineKey {
+    pub hdr: bool,
+    pub blend_mode: ImageBlendMode,
+}
+
+impl ImageBlendMode {
+    fn blend_state(self) -> BlendState {
+        match self {
+            ImageBlendMode::Alpha => BlendState::ALPHA_BLENDING,
+            ImageBlendMode::PremultipliedAlpha => BlendState::PREMULTIPLIED_ALPHA_BLENDING,
+            ImageBlendMode::Additive => BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            },
+            ImageBlendMode::Multiply => BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::Dst,
+                    dst_factor: BlendFactor::Zero,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            },
+            ImageBlendMode::Screen => BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::OneMinusSrc,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            },
+        }
+    }
+
+    /// Shader defs that prepare the fragment color for the blend state
+    fn shader_defs(self) -> Vec<String> {
+        match self {
+            ImageBlendMode::Alpha => vec![],
+            ImageBlendMode::PremultipliedAlpha => vec!["PREMULTIPLIED_TEXTURE".to_string()],
+            ImageBlendMode::Additive | ImageBlendMode::Screen => {
+                vec!["BLEND_PREMULTIPLY_ALPHA".to_string()]
+            }
+            ImageBlendMode::Multiply => vec!["BLEND_MULTIPLY".to_string()],
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for ExactImagePipeline {
+    type Key = ExactImagePipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let vertex_layout = VertexBufferLayout::from_vertex_formats(
+            VertexStepMode::Vertex,
+            vec![
+                // position
+                VertexFormat::Float32x3,
+                // uv
+                VertexFormat::Float32x2,
+                // color
+                VertexFormat::Float32x4,
+                // grayscale, brightness, contrast and saturation
+                VertexFormat::Float32x4,
+                // hue rotation
+                VertexFormat::Float32,
+                // uv bounds
+                VertexFormat::Float32x4,
+                // outline width or blur radius
+                VertexFormat::Float32x2,
+                // quad mode
+                VertexFormat::Uint32,
+            ],
+        );
+        let shader_defs = key.blend_mode.shader_defs();
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: super::EXACT_IMAGE_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: vec![vertex_layout],
+            },
+            fragment: Some(FragmentState {
+                shader: super::EXACT_IMAGE_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: if key.hdr {
+                        ViewTarget::TEXTURE_FORMAT_HDR
+                    } else {
+                        TextureFormat::bevy_default()
+                    },
+                    blend: Some(key.blend_mode.blend_state()),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: Some(vec![self.view_layout.clone(), self.image_layout.clone()]),
+            primitive: PrimitiveState {
+                front_face: FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: PolygonMode::Fill,
+                conservative: false,
+                topology: PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+            },
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("exact_image_pipeline".into()),
+        }
+    }
+}
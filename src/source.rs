@@ -0,0 +1,499 @@
+use bevy::asset::HandleId;
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::descriptor::ExactImageDescriptor;
+use crate::descriptor::NineSlice;
+use crate::diagnostics::InvalidImageFallback;
+use crate::import::ImportedAtlas;
+use crate::import::ImportedFrame;
+use crate::placeholder::LoadingPlaceholder;
+use crate::ExactAtlasImage;
+use crate::ExactImage;
+use crate::ExactSize;
+use crate::ImageAlignment;
+use crate::ImageBlendMode;
+
+/// Where the pixels of an [`ExactSourceImage`] come from
+#[derive(Clone, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImageSource {
+    /// The whole of a texture
+    Texture(#[cfg_attr(feature = "serde", serde(skip))] Handle<Image>),
+    /// A region of a texture, in pixels.
+    ///
+    /// The corners are kept as vectors rather than a [`Rect`], which scenes can't load back.
+    TextureRect {
+        #[cfg_attr(feature = "serde", serde(skip))]
+        texture: Handle<Image>,
+        min: Vec2,
+        max: Vec2,
+    },
+    /// A single frame of a texture atlas
+    Atlas {
+        #[cfg_attr(feature = "serde", serde(skip))]
+        atlas: Handle<TextureAtlas>,
+        index: usize,
+    },
+    /// An image that a camera renders to, or that is otherwise created and updated at runtime.
+    ///
+    /// Its size is checked every frame, so the node follows the target as it is resized.
+    RenderTarget(#[cfg_attr(feature = "serde", serde(skip))] Handle<Image>),
+    /// An image presented as described by an [`ExactImageDescriptor`].
+    ///
+    /// Once the descriptor has loaded, its size, alignment, rotation and color replace those of the
+    /// [`ExactSourceImage`] or [`ExactSprite`](crate::sprite::ExactSprite), and a
+    /// [`DescriptorAnimation`](crate::descriptor::DescriptorAnimation) on the entity selects the
+    /// frame of the clip it plays.
+    Descriptor {
+        #[cfg_attr(feature = "serde", serde(skip))]
+        descriptor: Handle<ExactImageDescriptor>,
+        /// Name of the clip drawn, or `None` for the descriptor's rect
+        clip: Option<String>,
+        /// Index of the clip's frame
+        frame: usize,
+    },
+}
+
+impl Default for ImageSource {
+    fn default() -> Self {
+        ImageSource::Texture(Default::default())
+    }
+}
+
+impl From<Handle<Image>> for ImageSource {
+    fn from(texture: Handle<Image>) -> Self {
+        ImageSource::Texture(texture)
+    }
+}
+
+impl From<Handle<ExactImageDescriptor>> for ImageSource {
+    fn from(descriptor: Handle<ExactImageDescriptor>) -> Self {
+        ImageSource::Descriptor {
+            descriptor,
+            clip: None,
+            frame: 0,
+        }
+    }
+}
+
+/// The region of its texture an [`ImageSource`] draws
+pub(crate) enum SourceRect {
+    /// The size of the region isn't known until an asset has loaded
+    Loading,
+    /// The atlas doesn't have a frame with the source's index
+    Invalid,
+    Ready(Rect),
+}
+
+impl ImageSource {
+    /// The region `rect` of a texture, in pixels
+    pub fn texture_rect(texture: Handle<Image>, rect: Rect) -> Self {
+        ImageSource::TextureRect {
+            texture,
+            min: rect.min,
+            max: rect.max,
+        }
+    }
+
+    /// Layout of the atlas frame before it was packed, once the imported atlas has loaded
+    pub(crate) fn imported_frame<'a>(
+        &self,
+        frames: Option<&Handle<ImportedAtlas>>,
+        imported_atlases: &'a Assets<ImportedAtlas>,
+    ) -> Option<&'a ImportedFrame> {
+        let ImageSource::Atlas { index, .. } = *self else {
+            return None;
+        };
+        imported_atlases.get(frames?)?.frame(index)
+    }
+
+    /// The texture the source draws from, `None` until its atlas or descriptor has loaded
+    pub(crate) fn texture<'a>(
+        &'a self,
+        atlases: &'a Assets<TextureAtlas>,
+        descriptors: &'a Assets<ExactImageDescriptor>,
+    ) -> Option<&'a Handle<Image>> {
+        match self {
+            ImageSource::Texture(texture)
+            | ImageSource::TextureRect { texture, .. }
+            | ImageSource::RenderTarget(texture) => Some(texture),
+            ImageSource::Atlas { atlas, .. } => atlases.get(atlas).map(|atlas| &atlas.texture),
+            ImageSource::Descriptor { descriptor, .. } => descriptors
+                .get(descriptor)
+                .map(|descriptor| &descriptor.texture),
+        }
+    }
+
+    /// Borders of the descriptor's nine-slice image, once it has loaded
+    pub(crate) fn nine_slice(
+        &self,
+        descriptors: &Assets<ExactImageDescriptor>,
+    ) -> Option<NineSlice> {
+        let ImageSource::Descriptor { descriptor, .. } = self else {
+            return None;
+        };
+        descriptors.get(descriptor)?.nine_slice
+    }
+
+    /// Size of the whole texture the source draws from
+    pub(crate) fn texture_size(
+        &self,
+        textures: &Assets<Image>,
+        atlases: &Assets<TextureAtlas>,
+        descriptors: &Assets<ExactImageDescriptor>,
+    ) -> Option<Vec2> {
+        match self {
+            ImageSource::Atlas { atlas, .. } => atlases.get(atlas).map(|atlas| atlas.size),
+            _ => textures
+                .get(self.texture(atlases, descriptors)?)
+                .map(|texture| texture.size()),
+        }
+    }
+
+    pub(crate) fn rect(
+        &self,
+        textures: &Assets<Image>,
+        atlases: &Assets<TextureAtlas>,
+        descriptors: &Assets<ExactImageDescriptor>,
+        fallback: InvalidImageFallback,
+    ) -> SourceRect {
+        match self {
+            ImageSource::Texture(texture) | ImageSource::RenderTarget(texture) => {
+                match textures.get(texture) {
+                    Some(texture) => SourceRect::Ready(Rect {
+                        min: Vec2::ZERO,
+                        max: texture.size(),
+                    }),
+                    None => SourceRect::Loading,
+                }
+            }
+            ImageSource::TextureRect { min, max, .. } => SourceRect::Ready(Rect {
+                min: *min,
+                max: *max,
+            }),
+            ImageSource::Atlas { atlas, index } => match atlases.get(atlas) {
+                Some(texture_atlas) => match fallback.atlas_rect(texture_atlas, *index) {
+                    Some(rect) => SourceRect::Ready(rect),
+                    None => SourceRect::Invalid,
+                },
+                None => SourceRect::Loading,
+            },
+            ImageSource::Descriptor {
+                descriptor,
+                clip,
+                frame,
+            } => match descriptors.get(descriptor).and_then(|descriptor| {
+                let texture = textures.get(&descriptor.texture)?;
+                Some(descriptor.source_rect(texture.size(), clip.as_deref(), *frame))
+            }) {
+                Some(rect) => SourceRect::Ready(rect),
+                None => SourceRect::Loading,
+            },
+        }
+    }
+
+    /// True once every asset needed to draw the source has loaded
+    pub(crate) fn is_loaded(
+        &self,
+        textures: &Assets<Image>,
+        atlases: &Assets<TextureAtlas>,
+        descriptors: &Assets<ExactImageDescriptor>,
+    ) -> bool {
+        matches!(self.texture(atlases, descriptors), Some(texture) if textures.contains(texture))
+    }
+
+    /// The asset of the source that failed to load, if any
+    pub(crate) fn failed(
+        &self,
+        asset_server: &AssetServer,
+        textures: &Assets<Image>,
+        atlases: &Assets<TextureAtlas>,
+        descriptors: &Assets<ExactImageDescriptor>,
+    ) -> Option<HandleUntyped> {
+        match self {
+            ImageSource::Atlas { atlas, .. } if !atlases.contains(atlas) => {
+                return (asset_server.get_load_state(atlas) == LoadState::Failed)
+                    .then(|| atlas.clone_weak_untyped());
+            }
+            ImageSource::Descriptor { descriptor, .. } if !descriptors.contains(descriptor) => {
+                return (asset_server.get_load_state(descriptor) == LoadState::Failed)
+                    .then(|| descriptor.clone_weak_untyped());
+            }
+            _ => {}
+        }
+        let texture = self.texture(atlases, descriptors)?;
+        (!textures.contains(texture) && asset_server.get_load_state(texture) == LoadState::Failed)
+            .then(|| texture.clone_weak_untyped())
+    }
+
+    /// Id of the asset that identifies the source, its atlas, descriptor or texture
+    pub(crate) fn id(&self) -> HandleId {
+        match self {
+            ImageSource::Texture(texture)
+            | ImageSource::TextureRect { texture, .. }
+            | ImageSource::RenderTarget(texture) => texture.id(),
+            ImageSource::Atlas { atlas, .. } => atlas.id(),
+            ImageSource::Descriptor { descriptor, .. } => descriptor.id(),
+        }
+    }
+
+    /// True if the source draws the whole of its texture
+    pub(crate) fn is_whole_texture(&self) -> bool {
+        matches!(self, ImageSource::Texture(_) | ImageSource::RenderTarget(_))
+    }
+
+    /// Index of the frame drawn from an atlas, which named frames and tags select
+    pub(crate) fn atlas_index_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            ImageSource::Atlas { index, .. } => Some(index),
+            _ => None,
+        }
+    }
+}
+
+/// An image drawn within a ui node, from any [`ImageSource`].
+///
+/// [`ExactImage`] and [`ExactAtlasImage`] are kept as wrappers, that the plugin copies into this component.
+#[derive(Component, Clone, Default, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default)]
+pub struct ExactSourceImage {
+    pub source: ImageSource,
+    /// rotation of the image in radians
+    pub rotation: Option<f32>,
+    pub alignment: ImageAlignment,
+    pub color: Color,
+    pub size: ExactSize,
+    /// How the image is blended with the ui drawn beneath it
+    pub blend_mode: ImageBlendMode,
+    /// Drawn in place of the image until its texture has loaded
+    pub loading_placeholder: Option<LoadingPlaceholder>,
+    /// Size of the texture assumed by the layout until it has loaded,
+    /// so that the node doesn't change size once it has
+    pub placeholder_size: Option<Vec2>,
+    /// Layout of the atlas frames before they were packed, only used by [`ImageSource::Atlas`].
+    ///
+    /// Trimmed frames are sized and aligned as if they were untrimmed,
+    /// so the image doesn't jitter as its frames change.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub frames: Option<Handle<ImportedAtlas>>,
+}
+
+/// Where an image is drawn, relative to the origin of the ui
+pub(crate) struct ImageGeometry {
+    /// Size of the image, including any trimmed borders of its atlas frame
+    pub size: Vec2,
+    /// Transform of the center of the image
+    pub transform: Mat4,
+    /// The point of the node that the image is aligned to
+    pub anchor: Vec2,
+    /// The point the image is rotated around
+    pub pivot: Vec2,
+}
+
+impl ImageGeometry {
+    /// Sizes, aligns and rotates an image within a box centered on the origin, with y pointing down
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn local(
+        size: ExactSize,
+        alignment: ImageAlignment,
+        rotation: Option<f32>,
+        frame: Option<&ImportedFrame>,
+        box_size: Vec2,
+        flex_direction: FlexDirection,
+        source_size: Vec2,
+        scale_factor: f32,
+    ) -> Self {
+        let size = size.compute(box_size, flex_direction, source_size, scale_factor);
+        let rotation = Mat4::from_rotation_z(rotation.unwrap_or(0.));
+        let anchor = alignment.offset(box_size, Vec2::ZERO);
+        let alignment_offset = alignment.offset(box_size, size);
+        match frame.and_then(|frame| frame.pivot) {
+            // the untrimmed frame is aligned as without a pivot, then rotated around the pivot
+            Some(pivot) => {
+                let pivot_offset = (pivot - Vec2::splat(0.5)) * size;
+                ImageGeometry {
+                    size,
+                    transform: Mat4::from_translation((alignment_offset + pivot_offset).extend(0.))
+                        * rotation
+                        * Mat4::from_translation((-pivot_offset).extend(0.)),
+                    anchor,
+                    pivot: alignment_offset + pivot_offset,
+                }
+            }
+            None => ImageGeometry {
+                size,
+                transform: Mat4::from_translation(alignment_offset.extend(0.)) * rotation,
+                anchor,
+                pivot: alignment_offset,
+            },
+        }
+    }
+
+    /// The geometry moved from its box to wherever the matrix places the box
+    pub(crate) fn transformed(self, matrix: Mat4) -> Self {
+        ImageGeometry {
+            size: self.size,
+            transform: matrix * self.transform,
+            anchor: matrix.transform_point3(self.anchor.extend(0.)).truncate(),
+            pivot: matrix.transform_point3(self.pivot.extend(0.)).truncate(),
+        }
+    }
+}
+
+impl ExactSourceImage {
+    /// Sizes and aligns the image within its node, given the size of its source
+    pub(crate) fn geometry(
+        &self,
+        frame: Option<&ImportedFrame>,
+        node: &Node,
+        style: &Style,
+        node_transform: &GlobalTransform,
+        source_size: Vec2,
+        scale_factor: f32,
+    ) -> ImageGeometry {
+        ImageGeometry::local(
+            self.size,
+            self.alignment,
+            self.rotation,
+            frame,
+            node.size(),
+            style.flex_direction,
+            source_size,
+            scale_factor,
+        )
+        .transformed(node_transform.compute_matrix())
+    }
+
+    /// Layout of the current atlas frame before it was packed, once the imported atlas has loaded
+    pub(crate) fn imported_frame<'a>(
+        &self,
+        imported_atlases: &'a Assets<ImportedAtlas>,
+    ) -> Option<&'a ImportedFrame> {
+        self.source
+            .imported_frame(self.frames.as_ref(), imported_atlases)
+    }
+}
+
+impl From<&ExactImage> for ExactSourceImage {
+    fn from(image: &ExactImage) -> Self {
+        Self {
+            source: ImageSource::Texture(image.texture.clone()),
+            rotation: image.rotation,
+            alignment: image.alignment,
+            color: image.color,
+            size: image.size,
+            blend_mode: image.blend_mode,
+            loading_placeholder: image.loading_placeholder.clone(),
+            placeholder_size: image.placeholder_size,
+            frames: None,
+        }
+    }
+}
+
+impl From<&ExactAtlasImage> for ExactSourceImage {
+    fn from(atlas_image: &ExactAtlasImage) -> Self {
+        Self {
+            source: ImageSource::Atlas {
+                atlas: atlas_image.atlas.clone(),
+                index: atlas_image.index,
+            },
+            rotation: atlas_image.rotation,
+            alignment: atlas_image.alignment,
+            color: atlas_image.color,
+            size: atlas_image.size,
+            blend_mode: atlas_image.blend_mode,
+            loading_placeholder: atlas_image.loading_placeholder.clone(),
+            placeholder_size: atlas_image.placeholder_size,
+            frames: atlas_image.frames.clone(),
+        }
+    }
+}
+
+/// Copies changed [`ExactImage`]s and [`ExactAtlasImage`]s into their [`ExactSourceImage`]
+#[allow(clippy::type_complexity)]
+pub fn sync_exact_image_sources(
+    mut commands: Commands,
+    removed_images: RemovedComponents<ExactImage>,
+    removed_atlas_images: RemovedComponents<ExactAtlasImage>,
+    mut changed: Query<
+        (
+            Entity,
+            Option<&ExactImage>,
+            Option<&ExactAtlasImage>,
+            Option<&mut ExactSourceImage>,
+        ),
+        Or<(Changed<ExactImage>, Changed<ExactAtlasImage>)>,
+    >,
+    wrapped: Query<(), Or<(With<ExactImage>, With<ExactAtlasImage>)>>,
+) {
+    for (id, image, atlas_image, source_image) in changed.iter_mut() {
+        let synced = match (image, atlas_image) {
+            (Some(image), _) => ExactSourceImage::from(image),
+            (None, Some(atlas_image)) => ExactSourceImage::from(atlas_image),
+            (None, None) => continue,
+        };
+        match source_image {
+            Some(mut source_image) => *source_image = synced,
+            None => {
+                commands.entity(id).insert(synced);
+            }
+        }
+    }
+    for id in removed_images.iter().chain(removed_atlas_images.iter()) {
+        if !wrapped.contains(id) {
+            if let Some(mut entity) = commands.get_entity(id) {
+                entity.remove::<ExactSourceImage>();
+            }
+        }
+    }
+}
+
+#[derive(Bundle)]
+pub struct ExactSourceImageBundle {
+    /// The image to render
+    pub image: ExactSourceImage,
+    /// Describes the size of the node
+    pub node: Node,
+    /// Describes the style including flexbox settings
+    pub style: Style,
+    /// The background color, which serves as a "fill" for this node
+    pub background_color: BackgroundColor,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The transform of the node
+    ///
+    /// This field is automatically managed by the UI layout system.
+    /// To alter the position of the `nodebundle`, use the properties of the [`Style`] component.
+    pub transform: Transform,
+    /// The global transform of the node
+    ///
+    /// This field is automatically managed by the UI layout system.
+    /// To alter the position of the `NodeBundle`, use the properties of the [`Style`] component.
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+    /// Indicates the depth at which the node should appear in the UI
+    pub z_index: ZIndex,
+}
+
+impl Default for ExactSourceImageBundle {
+    fn default() -> Self {
+        ExactSourceImageBundle {
+            image: Default::default(),
+            background_color: Color::NONE.into(),
+            node: Default::default(),
+            style: Default::default(),
+            focus_policy: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            computed_visibility: Default::default(),
+            z_index: Default::default(),
+        }
+    }
+}
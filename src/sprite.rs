@@ -0,0 +1,219 @@
+use bevy::prelude::*;
+use bevy::render::Extract;
+use bevy::sprite::ExtractedSprite;
+use bevy::sprite::ExtractedSprites;
+
+use crate::descriptor::DescriptorAnimation;
+use crate::descriptor::ExactImageDescriptor;
+use crate::diagnostics::InvalidImageFallback;
+use crate::import::ImportedAtlas;
+use crate::source::ImageGeometry;
+use crate::source::ImageSource;
+use crate::source::SourceRect;
+use crate::ExactSize;
+use crate::ImageAlignment;
+
+/// An exact image drawn in the world, sized and aligned within a box instead of a ui node.
+///
+/// The box is centered on the entity's transform, and the image follows the same
+/// [`ExactSize`] and [`ImageAlignment`] rules as in the ui, with top meaning up.
+/// Sizes are in world units, with one texture pixel to one unit like a [`Sprite`].
+///
+/// A sprite drawn from an [`ImageSource::Descriptor`] is sized, aligned, rotated, tinted
+/// and nine-sliced as the descriptor describes, and plays the clip of a [`DescriptorAnimation`].
+#[derive(Component, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default)]
+pub struct ExactSprite {
+    pub source: ImageSource,
+    /// Size of the box the image is drawn within.
+    ///
+    /// Without a box, it takes the size of the source like a ui node sized by its image.
+    pub box_size: Option<Vec2>,
+    /// `ForcePreserveAspectRatio` keeps the width of the box, like a node in a row
+    pub size: ExactSize,
+    pub alignment: ImageAlignment,
+    /// rotation of the image in radians, clockwise like in the ui
+    pub rotation: Option<f32>,
+    pub color: Color,
+    /// Layout of the atlas frames before they were packed
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub frames: Option<Handle<ImportedAtlas>>,
+}
+
+impl Default for ExactSprite {
+    fn default() -> Self {
+        Self {
+            source: Default::default(),
+            box_size: None,
+            size: Default::default(),
+            alignment: Default::default(),
+            rotation: None,
+            color: Color::WHITE,
+            frames: None,
+        }
+    }
+}
+
+impl ExactSprite {
+    pub fn new(source: impl Into<ImageSource>) -> Self {
+        Self {
+            source: source.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn box_size(mut self, box_size: Vec2) -> Self {
+        self.box_size = Some(box_size);
+        self
+    }
+
+    pub fn size(mut self, size: ExactSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn align(mut self, alignment: ImageAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Rotate the image by the given angle in radians
+    pub fn rotate(mut self, rotation: f32) -> Self {
+        self.rotation = Some(rotation);
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+#[derive(Bundle, Clone, Default)]
+pub struct ExactSpriteBundle {
+    pub sprite: ExactSprite,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    pub visibility: Visibility,
+    pub computed_visibility: ComputedVisibility,
+}
+
+/// Adds exact sprites to bevy's extracted sprites, so they're drawn and sorted with the other sprites
+#[allow(clippy::type_complexity)]
+pub fn extract_exact_sprites(
+    extracted_sprites: Option<ResMut<ExtractedSprites>>,
+    images: Extract<Res<Assets<Image>>>,
+    texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
+    imported_atlases: Extract<Res<Assets<ImportedAtlas>>>,
+    descriptors: Extract<Res<Assets<ExactImageDescriptor>>>,
+    fallback: Extract<Res<InvalidImageFallback>>,
+    sprite_query: Extract<
+        Query<(
+            Entity,
+            &ExactSprite,
+            &GlobalTransform,
+            &ComputedVisibility,
+            Option<&DescriptorAnimation>,
+        )>,
+    >,
+) {
+    let Some(mut extracted_sprites) = extracted_sprites else {
+        return;
+    };
+    // the geometry is computed with y pointing down like in the ui, then mirrored into the world
+    let flip = Mat4::from_scale(Vec3::new(1., -1., 1.));
+    for (entity, sprite, transform, visibility, animation) in sprite_query.iter() {
+        let descriptor = match &sprite.source {
+            ImageSource::Descriptor { descriptor, .. } => descriptors.get(descriptor),
+            _ => None,
+        };
+        // a descriptor's presentation replaces that of the sprite, as it does for ui nodes
+        let (exact_size, alignment, rotation, color) = match descriptor {
+            Some(descriptor) => (
+                descriptor.size,
+                descriptor.alignment,
+                descriptor.rotation,
+                descriptor.color,
+            ),
+            None => (sprite.size, sprite.alignment, sprite.rotation, sprite.color),
+        };
+        if !visibility.is_visible() || color.a() == 0. {
+            continue;
+        }
+        let Some(texture) = sprite
+            .source
+            .texture(&texture_atlases, &descriptors)
+            .filter(|texture| images.contains(texture))
+        else {
+            continue;
+        };
+        let SourceRect::Ready(rect) =
+            sprite
+                .source
+                .rect(&images, &texture_atlases, &descriptors, **fallback)
+        else {
+            continue;
+        };
+        let rect = descriptor
+            .zip(animation)
+            .and_then(|(descriptor, animation)| {
+                descriptor
+                    .clips
+                    .get(&animation.clip)?
+                    .frame(animation.elapsed)
+            })
+            .unwrap_or(rect);
+        let frame = sprite
+            .source
+            .imported_frame(sprite.frames.as_ref(), &imported_atlases);
+        let source_size = frame.map_or(rect.size(), |frame| frame.source_size);
+        let ImageGeometry {
+            size,
+            transform: mut local,
+            ..
+        } = ImageGeometry::local(
+            exact_size,
+            alignment,
+            rotation,
+            frame,
+            sprite.box_size.unwrap_or(source_size),
+            FlexDirection::Row,
+            source_size,
+            1.,
+        );
+        let world_transform = transform.compute_matrix();
+        if let Some(nine_slice) = descriptor.and_then(|descriptor| descriptor.nine_slice) {
+            for (source, target) in nine_slice.slices(rect, size, 1.) {
+                let local = local * Mat4::from_translation(target.center().extend(0.));
+                extracted_sprites.sprites.push(ExtractedSprite {
+                    entity,
+                    transform: (world_transform * flip * local * flip).into(),
+                    color,
+                    rect: Some(source),
+                    custom_size: Some(target.size()),
+                    image_handle_id: texture.id(),
+                    flip_x: false,
+                    flip_y: false,
+                    anchor: Vec2::ZERO,
+                });
+            }
+            continue;
+        }
+        let scale = size / source_size;
+        if let Some(frame) = frame {
+            local *= Mat4::from_translation((scale * frame.trimmed_center(rect.size())).extend(0.));
+        }
+        extracted_sprites.sprites.push(ExtractedSprite {
+            entity,
+            transform: (world_transform * flip * local * flip).into(),
+            color,
+            rect: Some(rect),
+            custom_size: Some(scale * rect.size()),
+            image_handle_id: texture.id(),
+            flip_x: false,
+            flip_y: false,
+            anchor: Vec2::ZERO,
+        });
+    }
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use bevy::ui::widget::ImageMode;
+
+use crate::source::ExactSourceImage;
+use crate::ExactImage;
+use crate::ExactImageBundle;
+use crate::ExactSize;
+
+/// Marks an entity with a [`UiImage`] to be converted into an [`ExactImage`] by [`replace_ui_images`].
+///
+/// The node keeps its size and its image looks the same, so a large ui can adopt exact images one node at a time.
+#[derive(Component, Copy, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct ReplaceUiImage;
+
+/// An exact image drawn like a [`UiImage`], which the ui sizes from its texture and stretches to fill the node.
+///
+/// The color is left white, as bevy tints a `UiImage` with the node's [`BackgroundColor`] instead.
+impl From<UiImage> for ExactImage {
+    fn from(image: UiImage) -> Self {
+        ExactImage::new(image.0).size(ExactSize::AttemptPreserveAspectRatio)
+    }
+}
+
+/// The tint of the bundle's background color moves to the image, and the background is cleared.
+impl From<ImageBundle> for ExactImageBundle {
+    fn from(bundle: ImageBundle) -> Self {
+        let image = ExactImage::from(bundle.image).color(bundle.background_color.0);
+        ExactImageBundle {
+            image,
+            node: bundle.node,
+            style: bundle.style,
+            background_color: Color::NONE.into(),
+            focus_policy: bundle.focus_policy,
+            transform: bundle.transform,
+            global_transform: bundle.global_transform,
+            visibility: bundle.visibility,
+            computed_visibility: bundle.computed_visibility,
+            z_index: bundle.z_index,
+        }
+    }
+}
+
+/// Replaces the [`UiImage`] of entities marked with [`ReplaceUiImage`] with an equivalent [`ExactImage`]
+#[allow(clippy::type_complexity)]
+pub fn replace_ui_images(
+    mut commands: Commands,
+    query: Query<(Entity, &UiImage, Option<&BackgroundColor>), With<ReplaceUiImage>>,
+) {
+    for (id, ui_image, background_color) in query.iter() {
+        let color = background_color.map_or(Color::WHITE, |background_color| background_color.0);
+        let image = ExactImage::from(ui_image.clone()).color(color);
+        commands
+            .entity(id)
+            .remove::<(UiImage, ImageMode, ReplaceUiImage)>()
+            .insert((
+                ExactSourceImage::from(&image),
+                image,
+                BackgroundColor(Color::NONE),
+            ));
+    }
+}
@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::FilterMode;
+use bevy::ui::ExtractedUiNode;
+use bevy_ui_exact_image::prelude::*;
+use bevy_ui_exact_image::render::batch_exact_images;
+use bevy_ui_exact_image::render::ExtractedExactImage;
+use bevy_ui_exact_image::render::QuadMode;
+
+/// A 32x16 image drawn at the origin, as the given node in the drawing order
+fn extracted(blend_mode: ImageBlendMode, color: Color, order: usize) -> ExtractedExactImage {
+    ExtractedExactImage {
+        node: ExtractedUiNode {
+            stack_index: order,
+            transform: Mat4::IDENTITY,
+            background_color: color,
+            rect: Rect::new(0., 0., 32., 16.),
+            image: Handle::default(),
+            atlas_size: None,
+            clip: None,
+            scale_factor: 1.,
+        },
+        blend_mode,
+        effects: ImageEffects::default(),
+        outline: None,
+        shadow: None,
+        sampling: None,
+        z: order as f32,
+        effects_z: order as f32 - 0.5,
+        order,
+    }
+}
+
+/// The color written by `BlendState::PREMULTIPLIED_ALPHA_BLENDING`
+fn blend_premultiplied(source: Vec4, target: Vec3) -> Vec3 {
+    source.truncate() + target * (1. - source.w)
+}
+
+#[test]
+fn premultiplied_images_are_faded_by_the_alpha_of_their_color() {
+    let color = Color::rgba(1., 1., 1., 0.5);
+    let batched = batch_exact_images([extracted(ImageBlendMode::PremultipliedAlpha, color, 0)]);
+    assert_eq!(batched.batches.len(), 1);
+    assert_eq!(batched.vertices.len(), 6);
+
+    // a white texel at half opacity, with its color multiplied by its alpha
+    let texel = Vec4::splat(0.5);
+    for vertex in &batched.vertices {
+        let source = Vec4::from(vertex.color) * texel;
+        // drawn at a quarter opacity, as if the texel and tint were alpha blended
+        for target in [Vec3::ZERO, Vec3::ONE] {
+            let blended = blend_premultiplied(source, target);
+            let expected = 0.25 * Vec3::ONE + 0.75 * target;
+            assert!(
+                (blended - expected).abs().max_element() < 1e-6,
+                "expected {expected}, got {blended}"
+            );
+        }
+    }
+}
+
+#[test]
+fn alpha_blended_images_keep_their_color() {
+    let color = Color::rgba(1., 1., 1., 0.5);
+    let batched = batch_exact_images([extracted(ImageBlendMode::Alpha, color, 0)]);
+    for vertex in &batched.vertices {
+        assert_eq!(vertex.color, [1., 1., 1., 0.5]);
+    }
+}
+
+#[test]
+fn images_are_only_batched_when_nothing_is_drawn_between_them() {
+    let batched = batch_exact_images(
+        [0, 1, 3].map(|order| extracted(ImageBlendMode::Additive, Color::WHITE, order)),
+    );
+    let batches: Vec<_> = batched
+        .batches
+        .iter()
+        .map(|batch| (batch.range.clone(), batch.z))
+        .collect();
+    // sorted by the depth of their last image, like bevy's ui batches
+    assert_eq!(batches, [(0..12, 1.), (12..18, 3.)]);
+}
+
+#[test]
+fn outlines_and_shadows_are_alpha_blended_beneath_their_image() {
+    let images = [0, 1].map(|order| ExtractedExactImage {
+        outline: Some(ImageOutline::default()),
+        shadow: Some(ImageShadow::default()),
+        ..extracted(ImageBlendMode::Additive, Color::WHITE, order)
+    });
+    let batched = batch_exact_images(images);
+    let batches: Vec<_> = batched
+        .batches
+        .iter()
+        .map(|batch| (batch.range.clone(), batch.blend_mode, batch.z))
+        .collect();
+    assert_eq!(
+        batches,
+        [
+            (0..12, ImageBlendMode::Alpha, -0.5),
+            (12..18, ImageBlendMode::Additive, 0.),
+            (18..30, ImageBlendMode::Alpha, 0.5),
+            (30..36, ImageBlendMode::Additive, 1.),
+        ]
+    );
+    let modes: Vec<_> = batched
+        .vertices
+        .chunks(6)
+        .map(|quad| quad[0].mode)
+        .collect();
+    let [image, outline, shadow] =
+        [QuadMode::Image, QuadMode::Outline, QuadMode::Shadow].map(|mode| mode as u32);
+    assert_eq!(modes, [shadow, outline, image, shadow, outline, image]);
+}
+
+#[test]
+fn images_with_the_same_sampling_share_a_batch_and_bind_group() {
+    let samplings = [
+        None,
+        Some(ImageSampling::Nearest),
+        Some(ImageSampling::Nearest),
+        Some(ImageSampling::LinearMipmapped),
+    ];
+    let mut order = 0..;
+    let images = samplings.map(|sampling| ExtractedExactImage {
+        sampling,
+        ..extracted(ImageBlendMode::Alpha, Color::WHITE, order.next().unwrap())
+    });
+    let batched = batch_exact_images(images);
+    let batches: Vec<_> = batched
+        .batches
+        .iter()
+        .map(|batch| (batch.range.clone(), batch.bind_group_key()))
+        .collect();
+    assert_eq!(
+        batches,
+        [
+            (0..6, (Handle::default(), None)),
+            (6..18, (Handle::default(), Some(ImageSampling::Nearest))),
+            (
+                18..24,
+                (Handle::default(), Some(ImageSampling::LinearMipmapped))
+            ),
+        ]
+    );
+}
+
+#[test]
+fn sampling_overrides_choose_the_filters_of_the_sampler() {
+    let nearest = ImageSampling::Nearest.sampler_descriptor();
+    assert_eq!(nearest.mag_filter, FilterMode::Nearest);
+    assert_eq!(nearest.min_filter, FilterMode::Nearest);
+
+    let linear = ImageSampling::Linear.sampler_descriptor();
+    assert_eq!(linear.mag_filter, FilterMode::Linear);
+    assert_eq!(linear.min_filter, FilterMode::Linear);
+    // only the full size texture is sampled
+    assert_eq!(linear.lod_max_clamp, 0.);
+
+    let mipmapped = ImageSampling::LinearMipmapped.sampler_descriptor();
+    assert_eq!(mipmapped.min_filter, FilterMode::Linear);
+    assert_eq!(mipmapped.mipmap_filter, FilterMode::Linear);
+    assert!(mipmapped.lod_max_clamp > 0.);
+}
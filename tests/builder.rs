@@ -0,0 +1,105 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+use common::*;
+
+#[test]
+fn builder_sets_fields() {
+    let texture = Handle::weak(bevy::asset::HandleId::random::<Image>());
+    let image = ExactImage::new(texture.clone())
+        .size(ExactSize::Exactly(Vec2::new(30., 20.)))
+        .align(ImageAlignment::TopLeft)
+        .rotate(1.)
+        .color(Color::RED)
+        .blend_mode(ImageBlendMode::Additive)
+        .placeholder_size(Vec2::new(4., 2.));
+    assert!(image.texture == texture);
+    assert!(matches!(image.size, ExactSize::Exactly(size) if size == Vec2::new(30., 20.)));
+    assert!(matches!(image.alignment, ImageAlignment::TopLeft));
+    assert_eq!(image.rotation, Some(1.));
+    assert_eq!(image.color, Color::RED);
+    assert!(image.blend_mode == ImageBlendMode::Additive);
+    assert_eq!(image.placeholder_size, Some(Vec2::new(4., 2.)));
+
+    let atlas = Handle::weak(bevy::asset::HandleId::random::<TextureAtlas>());
+    let image = ExactAtlasImage::new(atlas.clone(), 5)
+        .size(ExactSize::FillNode)
+        .align(ImageAlignment::BottomRight)
+        .rotate(-1.);
+    assert!(image.atlas == atlas);
+    assert_eq!(image.index, 5);
+    assert!(matches!(image.size, ExactSize::FillNode));
+    assert!(matches!(image.alignment, ImageAlignment::BottomRight));
+    assert_eq!(image.rotation, Some(-1.));
+}
+
+#[derive(Resource)]
+struct Spawned {
+    image: Entity,
+    atlas_image: Entity,
+    parent: Entity,
+    child: Entity,
+}
+
+#[test]
+fn spawn_extensions_spawn_bundles() {
+    let mut harness = Harness::new();
+    harness.app.add_startup_system(|mut commands: Commands| {
+        let image = commands
+            .spawn_exact_image(ExactImage::new(Default::default()).size(ExactSize::Texture))
+            .insert(Style {
+                size: Size::new(Val::Px(50.), Val::Px(40.)),
+                ..Default::default()
+            })
+            .id();
+        let atlas_image = commands
+            .spawn_exact_atlas_image(ExactAtlasImage::new(Default::default(), 3))
+            .id();
+        let mut child = None;
+        let parent = commands
+            .spawn(NodeBundle::default())
+            .with_children(|parent| {
+                child = Some(
+                    parent
+                        .spawn_exact_atlas_image(ExactAtlasImage::new(Default::default(), 1))
+                        .id(),
+                );
+            })
+            .id();
+        commands.insert_resource(Spawned {
+            image,
+            atlas_image,
+            parent,
+            child: child.unwrap(),
+        });
+    });
+    harness.update();
+
+    let world = &harness.app.world;
+    let spawned = world.resource::<Spawned>();
+    assert!(world.get::<Node>(spawned.image).is_some());
+    assert_eq!(
+        world.get::<Style>(spawned.image).unwrap().size,
+        Size::new(Val::Px(50.), Val::Px(40.))
+    );
+    assert!(matches!(
+        world.get::<ExactSourceImage>(spawned.image).unwrap().size,
+        ExactSize::Texture
+    ));
+    assert!(matches!(
+        world
+            .get::<ExactSourceImage>(spawned.atlas_image)
+            .unwrap()
+            .source,
+        ImageSource::Atlas { index: 3, .. }
+    ));
+    assert_eq!(
+        world.get::<Parent>(spawned.child).unwrap().get(),
+        spawned.parent
+    );
+    assert!(matches!(
+        world.get::<ExactSourceImage>(spawned.child).unwrap().source,
+        ImageSource::Atlas { index: 1, .. }
+    ));
+}
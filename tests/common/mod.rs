@@ -0,0 +1,277 @@
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use bevy::asset::Asset;
+use bevy::asset::LoadState;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::render::primitives::Frustum;
+use bevy::render::render_resource::Extent3d;
+use bevy::render::render_resource::TextureDimension;
+use bevy::render::render_resource::TextureFormat;
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy::render::view::VisibilityPlugin;
+use bevy::render::view::VisibleEntities;
+use bevy::render::MainWorld;
+use bevy::render::RenderApp;
+use bevy::render::RenderStage;
+use bevy::sprite::extract_sprites;
+use bevy::sprite::ExtractedSprite;
+use bevy::sprite::ExtractedSprites;
+use bevy::sprite::SpriteSystem;
+use bevy::text::TextPlugin;
+use bevy::ui::extract_uinodes;
+use bevy::ui::ExtractedUiNode;
+use bevy::ui::ExtractedUiNodes;
+use bevy::ui::RenderUiSystem;
+use bevy::ui::UiPlugin;
+use bevy::window::WindowId;
+use bevy::window::WindowPlugin;
+use bevy_ui_exact_image::prelude::*;
+#[cfg(feature = "rasterizer")]
+use bevy_ui_exact_image::raster::Raster;
+use bevy_ui_exact_image::render::ExtractedExactImage;
+use bevy_ui_exact_image::render::ExtractedExactImages;
+use bevy_ui_exact_image::render::EXACT_IMAGE_MARKER_HANDLE;
+
+pub const WINDOW_SIZE: Vec2 = Vec2::new(800., 600.);
+
+/// An app that lays out the ui and runs the extract stage of a render sub-app that has no gpu
+pub struct Harness {
+    pub app: App,
+    pub window_size: Vec2,
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        Self::with_window_size(WINDOW_SIZE)
+    }
+
+    pub fn with_window_size(window_size: Vec2) -> Self {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(TransformPlugin)
+            .add_plugin(HierarchyPlugin)
+            .add_plugin(WindowPlugin::default())
+            .add_plugin(InputPlugin)
+            .add_plugin(AssetPlugin {
+                asset_folder: "tests/fixtures".to_string(),
+                watch_for_changes: false,
+            })
+            .add_asset::<Shader>()
+            .add_asset::<Image>()
+            .add_asset::<Mesh>()
+            .add_asset::<TextureAtlas>()
+            .add_plugin(VisibilityPlugin)
+            .add_plugin(TextPlugin)
+            .add_plugin(UiPlugin);
+
+        let mut render_app = App::empty();
+        render_app
+            .add_stage(RenderStage::Extract, SystemStage::parallel())
+            .init_resource::<ExtractedUiNodes>()
+            .init_resource::<ExtractedSprites>()
+            .add_system_to_stage(
+                RenderStage::Extract,
+                extract_uinodes.label(RenderUiSystem::ExtractNode),
+            )
+            .add_system_to_stage(
+                RenderStage::Extract,
+                extract_sprites.label(SpriteSystem::ExtractSprites),
+            );
+        app.add_sub_app(RenderApp, render_app, extract);
+        app.add_plugin(ExactImagePlugin);
+
+        app.world.resource_mut::<Windows>().add(Window::new(
+            WindowId::primary(),
+            &WindowDescriptor {
+                width: window_size.x,
+                height: window_size.y,
+                ..Default::default()
+            },
+            window_size.x as u32,
+            window_size.y as u32,
+            1.,
+            None,
+            None,
+        ));
+        // drawn by bevy for the background of nodes without an image
+        app.world
+            .resource_mut::<Assets<Image>>()
+            .set_untracked(DEFAULT_IMAGE_HANDLE, Image::default());
+        // ui nodes are only visible to cameras
+        app.world.spawn((
+            Camera::default(),
+            VisibleEntities::default(),
+            Frustum::default(),
+        ));
+        Self { app, window_size }
+    }
+
+    pub fn add_image(&mut self, image: Image) -> Handle<Image> {
+        self.app.world.resource_mut::<Assets<Image>>().add(image)
+    }
+
+    pub fn add_atlas(&mut self, atlas: TextureAtlas) -> Handle<TextureAtlas> {
+        self.app
+            .world
+            .resource_mut::<Assets<TextureAtlas>>()
+            .add(atlas)
+    }
+
+    /// Loads an asset from `tests/fixtures`, updating the app until it has loaded or failed to
+    pub fn load<T: Asset>(&mut self, path: &str) -> (Handle<T>, LoadState) {
+        let handle = self.app.world.resource::<AssetServer>().load(path);
+        for _ in 0..1000 {
+            self.update();
+            let state = self
+                .app
+                .world
+                .resource::<AssetServer>()
+                .get_load_state(&handle);
+            if matches!(state, LoadState::Loaded | LoadState::Failed) {
+                return (handle, state);
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        panic!("{path} didn't load");
+    }
+
+    pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+        self.app.world.spawn(bundle).id()
+    }
+
+    pub fn update(&mut self) {
+        self.app.update();
+    }
+
+    /// The size of the entity's image that its node is laid out with, if it has one
+    pub fn calculated_size(&self, entity: Entity) -> Option<Size> {
+        self.app
+            .world
+            .get::<CalculatedSize>(entity)
+            .map(|calculated_size| calculated_size.size)
+    }
+
+    /// Nodes drawn by bevy's ui renderer in the last frame, without the markers left by exact images
+    pub fn extracted_nodes(&self) -> Vec<&ExtractedUiNode> {
+        let marker = EXACT_IMAGE_MARKER_HANDLE.typed_weak::<Image>();
+        self.all_extracted_nodes()
+            .iter()
+            .filter(|node| node.image != marker)
+            .collect()
+    }
+
+    /// Every node extracted for bevy's ui renderer in the last frame, in drawing order
+    pub fn all_extracted_nodes(&self) -> &[ExtractedUiNode] {
+        &self.render_world().resource::<ExtractedUiNodes>().uinodes
+    }
+
+    /// Sprites drawn by bevy's sprite renderer in the last frame
+    pub fn extracted_sprites(&self) -> &[ExtractedSprite] {
+        &self.render_world().resource::<ExtractedSprites>().sprites
+    }
+
+    /// Images drawn by the exact image renderer in the last frame
+    pub fn extracted_exact_images(&self) -> &[ExtractedExactImage] {
+        &self
+            .render_world()
+            .resource::<ExtractedExactImages>()
+            .images
+    }
+
+    /// Draws the nodes extracted in the last frame on the cpu
+    #[cfg(feature = "rasterizer")]
+    pub fn rasterize(&self) -> Raster {
+        Raster::rasterize(
+            self.window_size.x as u32,
+            self.window_size.y as u32,
+            self.all_extracted_nodes(),
+            self.app.world.resource::<Assets<Image>>(),
+        )
+    }
+
+    fn render_world(&self) -> &World {
+        &self.app.get_sub_app(RenderApp).ok().unwrap().world
+    }
+}
+
+/// Runs the extract stage with the main world, like bevy's renderer does
+fn extract(app_world: &mut World, render_app: &mut App) {
+    // these are drained by the exact image renderer, which the harness doesn't have
+    render_app
+        .world
+        .resource_mut::<ExtractedExactImages>()
+        .images
+        .clear();
+    let extract = render_app
+        .schedule
+        .get_stage_mut::<SystemStage>(RenderStage::Extract)
+        .unwrap();
+    let mut main_world = MainWorld::default();
+    std::mem::swap(&mut *main_world, app_world);
+    render_app.world.insert_resource(main_world);
+    extract.run(&mut render_app.world);
+    let mut main_world = render_app.world.remove_resource::<MainWorld>().unwrap();
+    std::mem::swap(&mut *main_world, app_world);
+    extract.apply_buffers(&mut render_app.world);
+}
+
+pub fn image(width: u32, height: u32) -> Image {
+    Image::new_fill(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[255, 255, 255, 255],
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+/// A texture with red, green, blue and white quadrants, clockwise from the top left
+pub fn quadrant_image(width: u32, height: u32) -> Image {
+    let mut image = image(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            let color: [u8; 4] = match (2 * x < width, 2 * y < height) {
+                (true, true) => [255, 0, 0, 255],
+                (false, true) => [0, 255, 0, 255],
+                (false, false) => [0, 0, 255, 255],
+                (true, false) => [255, 255, 255, 255],
+            };
+            let offset = 4 * (y * width + x) as usize;
+            image.data[offset..offset + 4].copy_from_slice(&color);
+        }
+    }
+    image
+}
+
+/// An absolutely positioned node with the given position and size in pixels
+pub fn node_style(position: Vec2, size: Vec2) -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        position: UiRect {
+            left: Val::Px(position.x),
+            top: Val::Px(position.y),
+            ..Default::default()
+        },
+        size: Size::new(Val::Px(size.x), Val::Px(size.y)),
+        ..Default::default()
+    }
+}
+
+/// Position of the center of an extracted quad
+pub fn translation(node: &ExtractedUiNode) -> Vec2 {
+    node.transform.w_axis.truncate().truncate()
+}
+
+#[track_caller]
+pub fn assert_near(actual: Vec2, expected: Vec2) {
+    assert!(
+        (actual - expected).abs().max_element() < 1e-3,
+        "expected {expected}, got {actual}"
+    );
+}
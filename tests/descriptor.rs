@@ -0,0 +1,272 @@
+mod common;
+
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_ui_exact_image::descriptor::ImageClip;
+use bevy_ui_exact_image::descriptor::NineSlice;
+use bevy_ui_exact_image::prelude::*;
+use common::*;
+
+const BORDERS: NineSlice = NineSlice {
+    left: 4.,
+    right: 6.,
+    top: 2.,
+    bottom: 8.,
+};
+
+/// Two 24x24 frames, shown for a quarter of a second each
+fn blink(looping: bool) -> ImageClip {
+    ImageClip {
+        frames: vec![Rect::new(24., 0., 48., 24.), Rect::new(48., 0., 72., 24.)],
+        frames_per_second: 4.,
+        looping,
+    }
+}
+
+fn descriptor(texture: Handle<Image>) -> ExactImageDescriptor {
+    ExactImageDescriptor {
+        texture,
+        rect: Some(Rect::new(0., 0., 24., 24.)),
+        size: ExactSize::Texture,
+        alignment: ImageAlignment::Center,
+        rotation: None,
+        color: Color::WHITE,
+        nine_slice: None,
+        clips: HashMap::from_iter([("blink".to_string(), blink(true))]),
+    }
+}
+
+#[test]
+fn descriptor_files_are_parsed() {
+    let mut harness = Harness::new();
+    let (handle, state) = harness.load::<ExactImageDescriptor>("panel.exactimage.ron");
+    assert_eq!(state, LoadState::Loaded);
+    let texture = harness
+        .app
+        .world
+        .resource::<AssetServer>()
+        .get_handle::<Image, _>("panel.png");
+    let descriptors = harness.app.world.resource::<Assets<ExactImageDescriptor>>();
+    let descriptor = descriptors.get(&handle).unwrap();
+    assert_eq!(descriptor.texture, texture);
+    assert_eq!(descriptor.rect, Some(Rect::new(0., 0., 24., 24.)));
+    assert!(descriptor.size == ExactSize::Exactly(Vec2::new(48., 32.)));
+    assert!(descriptor.alignment == ImageAlignment::TopLeft);
+    assert_eq!(descriptor.rotation, Some(0.5));
+    assert_eq!(descriptor.color, Color::rgba(1., 0.5, 0.25, 1.));
+    assert_eq!(descriptor.nine_slice, Some(BORDERS));
+    assert_eq!(descriptor.clips.len(), 1);
+    let clip = &descriptor.clips["blink"];
+    assert_eq!(clip.frames, blink(true).frames);
+    assert_eq!(clip.frames_per_second, 4.);
+    assert!(clip.looping);
+}
+
+#[test]
+fn descriptor_files_default_everything_but_the_texture() {
+    let mut harness = Harness::new();
+    let (handle, state) = harness.load::<ExactImageDescriptor>("minimal.exactimage.ron");
+    assert_eq!(state, LoadState::Loaded);
+    let descriptors = harness.app.world.resource::<Assets<ExactImageDescriptor>>();
+    let descriptor = descriptors.get(&handle).unwrap();
+    assert_eq!(descriptor.rect, None);
+    assert!(descriptor.size == ExactSize::default());
+    assert!(descriptor.alignment == ImageAlignment::default());
+    assert_eq!(descriptor.rotation, None);
+    assert_eq!(descriptor.color, Color::WHITE);
+    assert_eq!(descriptor.nine_slice, None);
+    assert!(descriptor.clips.is_empty());
+
+    let (_, state) = harness.load::<ExactImageDescriptor>("unknown_field.exactimage.ron");
+    assert_eq!(state, LoadState::Failed);
+}
+
+#[test]
+fn nine_slices_keep_their_corners_and_stretch_the_rest() {
+    let rect = Rect::new(0., 0., 24., 24.);
+    let slices: Vec<_> = BORDERS.slices(rect, Vec2::new(60., 40.), 2.).collect();
+    let rows = [
+        (0., 2., -20., -16.),
+        (2., 16., -16., 4.),
+        (16., 24., 4., 20.),
+    ];
+    let columns = [
+        (0., 4., -30., -22.),
+        (4., 18., -22., 18.),
+        (18., 24., 18., 30.),
+    ];
+    let expected: Vec<_> = rows
+        .iter()
+        .flat_map(|&(source_top, source_bottom, top, bottom)| {
+            columns
+                .iter()
+                .map(move |&(source_left, source_right, left, right)| {
+                    (
+                        Rect::new(source_left, source_top, source_right, source_bottom),
+                        Rect::new(left, top, right, bottom),
+                    )
+                })
+        })
+        .collect();
+    assert_eq!(slices, expected);
+}
+
+#[test]
+fn nine_slice_borders_shrink_to_fit_small_images() {
+    let rect = Rect::new(0., 0., 24., 24.);
+    // the 10 pixels of left and right borders are drawn in 5, without a center column
+    let slices: Vec<_> = BORDERS.slices(rect, Vec2::new(5., 40.), 1.).collect();
+    assert_eq!(slices.len(), 6);
+    for (source, target) in slices {
+        let (source_columns, target_columns) = if source.min.x == 0. {
+            ((0., 4.), (-2.5, -0.5))
+        } else {
+            ((18., 24.), (-0.5, 2.5))
+        };
+        assert_eq!((source.min.x, source.max.x), source_columns);
+        assert_eq!((target.min.x, target.max.x), target_columns);
+    }
+}
+
+#[test]
+fn clips_select_frames_by_elapsed_time() {
+    let looping = blink(true);
+    let once = blink(false);
+    for (elapsed, looping_index, once_index) in [
+        (-1., 0, 0),
+        (0., 0, 0),
+        (0.3, 1, 1),
+        (0.5, 0, 1),
+        (10., 0, 1),
+    ] {
+        assert_eq!(
+            looping.frame_index(elapsed),
+            Some(looping_index),
+            "{elapsed}"
+        );
+        assert_eq!(once.frame_index(elapsed), Some(once_index), "{elapsed}");
+    }
+    assert_eq!(looping.frame(0.3), Some(Rect::new(48., 0., 72., 24.)));
+    assert_eq!(ImageClip::default().frame_index(0.), None);
+}
+
+#[test]
+fn source_rects_are_taken_from_clips_then_the_rect_then_the_texture() {
+    let texture_size = Vec2::new(96., 24.);
+    let mut descriptor = descriptor(Handle::default());
+    assert_eq!(
+        descriptor.source_rect(texture_size, Some("blink"), 1),
+        Rect::new(48., 0., 72., 24.)
+    );
+    // clips and frames that don't exist fall back to the rect
+    let rect = Rect::new(0., 0., 24., 24.);
+    assert_eq!(descriptor.source_rect(texture_size, Some("blink"), 2), rect);
+    assert_eq!(descriptor.source_rect(texture_size, Some("wave"), 0), rect);
+    assert_eq!(descriptor.source_rect(texture_size, None, 1), rect);
+
+    descriptor.rect = None;
+    assert_eq!(
+        descriptor.source_rect(texture_size, None, 0),
+        Rect::new(0., 0., 96., 24.)
+    );
+}
+
+#[test]
+fn animations_draw_the_frame_of_their_clip() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(96, 24));
+    let descriptor = harness
+        .app
+        .world
+        .resource_mut::<Assets<ExactImageDescriptor>>()
+        .add(descriptor(texture));
+    harness.spawn((
+        ExactSourceImageBundle {
+            image: ExactSourceImage {
+                source: descriptor.into(),
+                ..Default::default()
+            },
+            style: node_style(Vec2::ZERO, Vec2::splat(40.)),
+            ..Default::default()
+        },
+        DescriptorAnimation {
+            clip: "blink".to_string(),
+            elapsed: 0.3,
+        },
+    ));
+    harness.update();
+    let nodes = harness.extracted_nodes();
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].rect, Rect::new(48., 0., 72., 24.));
+}
+
+#[test]
+fn nine_slice_images_are_drawn_a_quad_per_slice() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(96, 24));
+    let descriptor = harness
+        .app
+        .world
+        .resource_mut::<Assets<ExactImageDescriptor>>()
+        .add(ExactImageDescriptor {
+            size: ExactSize::FillNode,
+            nine_slice: Some(BORDERS),
+            ..descriptor(texture)
+        });
+    // too narrow for the left and right borders
+    harness.spawn(ExactSourceImageBundle {
+        image: ExactSourceImage {
+            source: descriptor.into(),
+            ..Default::default()
+        },
+        style: node_style(Vec2::ZERO, Vec2::new(5., 40.)),
+        ..Default::default()
+    });
+    harness.update();
+    let expected: Vec<_> = BORDERS
+        .slices(Rect::new(0., 0., 24., 24.), Vec2::new(5., 40.), 1.)
+        .map(|(source, _)| source)
+        .collect();
+    let drawn: Vec<_> = harness
+        .extracted_nodes()
+        .into_iter()
+        .map(|node| node.rect)
+        .collect();
+    assert_eq!(drawn, expected);
+}
+
+#[test]
+fn descriptor_handles_on_ui_nodes_are_drawn() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(96, 24));
+    let descriptor = harness
+        .app
+        .world
+        .resource_mut::<Assets<ExactImageDescriptor>>()
+        .add(descriptor(texture));
+    let entity = harness.spawn((
+        NodeBundle {
+            style: node_style(Vec2::ZERO, Vec2::splat(40.)),
+            background_color: Color::NONE.into(),
+            ..Default::default()
+        },
+        descriptor,
+    ));
+    harness.update();
+    let drawn: Vec<_> = harness
+        .extracted_nodes()
+        .into_iter()
+        .filter(|node| node.background_color != Color::NONE)
+        .map(|node| node.rect)
+        .collect();
+    assert_eq!(drawn, [Rect::new(0., 0., 24., 24.)]);
+
+    harness
+        .app
+        .world
+        .entity_mut(entity)
+        .remove::<Handle<ExactImageDescriptor>>();
+    harness.update();
+    assert!(harness.app.world.get::<ExactSourceImage>(entity).is_none());
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy::render::render_resource::Extent3d;
+use bevy_ui_exact_image::prelude::*;
+use common::*;
+
+fn extent(width: u32, height: u32) -> Extent3d {
+    Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    }
+}
+
+#[test]
+fn resized_render_target_resizes_node_in_one_frame() {
+    let mut harness = Harness::new();
+    let target = harness.add_image(image(64, 32));
+    let entity = harness.spawn(ExactSourceImageBundle {
+        image: ExactSourceImage {
+            source: ImageSource::RenderTarget(target.clone()),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    harness.update();
+    assert_eq!(
+        harness.calculated_size(entity),
+        Some(Size::new(Val::Px(64.), Val::Px(32.)))
+    );
+
+    for (width, height) in [(128, 96), (16, 48)] {
+        harness
+            .app
+            .world
+            .resource_mut::<Assets<Image>>()
+            .get_mut(&target)
+            .unwrap()
+            .resize(extent(width, height));
+        harness.update();
+        assert_eq!(
+            harness.calculated_size(entity),
+            Some(Size::new(Val::Px(width as f32), Val::Px(height as f32)))
+        );
+    }
+}
+
+#[test]
+fn procedurally_resized_texture_resizes_node() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(8, 8));
+    let entity = harness.spawn(ExactImageBundle {
+        image: ExactImage {
+            texture: texture.clone(),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    harness.update();
+
+    harness
+        .app
+        .world
+        .resource_mut::<Assets<Image>>()
+        .get_mut(&texture)
+        .unwrap()
+        .resize(extent(40, 20));
+    harness.update();
+    harness.update();
+    assert_eq!(
+        harness.calculated_size(entity),
+        Some(Size::new(Val::Px(40.), Val::Px(20.)))
+    );
+
+    // updating the pixels without resizing leaves the layout alone
+    harness
+        .app
+        .world
+        .resource_mut::<Assets<Image>>()
+        .get_mut(&texture)
+        .unwrap()
+        .data
+        .fill(0);
+    harness.update();
+    harness.update();
+    assert_eq!(
+        harness.calculated_size(entity),
+        Some(Size::new(Val::Px(40.), Val::Px(20.)))
+    );
+}
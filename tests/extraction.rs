@@ -0,0 +1,426 @@
+mod common;
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+use bevy_ui_exact_image::render::EXACT_IMAGE_MARKER_HANDLE;
+use common::*;
+
+const NODE_POSITION: Vec2 = Vec2::new(100., 50.);
+const NODE_SIZE: Vec2 = Vec2::new(200., 150.);
+const NODE_CENTER: Vec2 = Vec2::new(200., 125.);
+const TEXTURE_SIZE: (u32, u32) = (32, 16);
+const COLOR: Color = Color::rgba(0.25, 0.5, 0.75, 0.5);
+
+/// Size of an image drawn from a 32x16 texture in a 200x150 node
+fn expected_size(size: ExactSize) -> Vec2 {
+    match size {
+        ExactSize::AttemptPreserveAspectRatio | ExactSize::FillNode => NODE_SIZE,
+        ExactSize::ForcePreserveAspectRatio => Vec2::new(200., 100.),
+        ExactSize::Texture => Vec2::new(32., 16.),
+        ExactSize::Scaled(_) => Vec2::new(64., 8.),
+        ExactSize::Exactly(_) => Vec2::new(10., 20.),
+    }
+}
+
+/// Offset from the center of a 200x150 node to the center of an image of the given size
+fn expected_offset(alignment: ImageAlignment, size: Vec2) -> Vec2 {
+    let left = -100. + 0.5 * size.x;
+    let right = 100. - 0.5 * size.x;
+    let top = -75. + 0.5 * size.y;
+    let bottom = 75. - 0.5 * size.y;
+    match alignment {
+        ImageAlignment::TopLeft => Vec2::new(left, top),
+        ImageAlignment::TopCenter => Vec2::new(0., top),
+        ImageAlignment::TopRight => Vec2::new(right, top),
+        ImageAlignment::CenterLeft => Vec2::new(left, 0.),
+        ImageAlignment::Center => Vec2::ZERO,
+        ImageAlignment::CenterRight => Vec2::new(right, 0.),
+        ImageAlignment::BottomLeft => Vec2::new(left, bottom),
+        ImageAlignment::BottomCenter => Vec2::new(0., bottom),
+        ImageAlignment::BottomRight => Vec2::new(right, bottom),
+        // the x anchor is a fraction of the node's width, the y anchor of its half height
+        ImageAlignment::Anchor(_) => Vec2::new(50., -37.5),
+    }
+}
+
+const SIZES: [ExactSize; 6] = [
+    ExactSize::AttemptPreserveAspectRatio,
+    ExactSize::ForcePreserveAspectRatio,
+    ExactSize::FillNode,
+    ExactSize::Texture,
+    ExactSize::Scaled(Vec2::new(2., 0.5)),
+    ExactSize::Exactly(Vec2::new(10., 20.)),
+];
+
+const ALIGNMENTS: [ImageAlignment; 10] = [
+    ImageAlignment::TopLeft,
+    ImageAlignment::TopCenter,
+    ImageAlignment::TopRight,
+    ImageAlignment::CenterLeft,
+    ImageAlignment::Center,
+    ImageAlignment::CenterRight,
+    ImageAlignment::BottomLeft,
+    ImageAlignment::BottomCenter,
+    ImageAlignment::BottomRight,
+    ImageAlignment::Anchor(Vec2::new(0.25, -0.5)),
+];
+
+fn spawn_image(harness: &mut Harness, image: ExactImage) -> Entity {
+    harness.spawn(ExactImageBundle {
+        image,
+        style: node_style(NODE_POSITION, NODE_SIZE),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn every_size_and_alignment() {
+    for size in SIZES {
+        for alignment in ALIGNMENTS {
+            let mut harness = Harness::new();
+            let texture = harness.add_image(image(TEXTURE_SIZE.0, TEXTURE_SIZE.1));
+            spawn_image(
+                &mut harness,
+                ExactImage {
+                    texture: texture.clone(),
+                    size,
+                    alignment,
+                    color: COLOR,
+                    ..Default::default()
+                },
+            );
+            harness.update();
+
+            let nodes = harness.extracted_nodes();
+            assert_eq!(nodes.len(), 1);
+            let node = &nodes[0];
+            let image_size = expected_size(size);
+            assert_near(
+                translation(node),
+                NODE_CENTER + expected_offset(alignment, image_size),
+            );
+            assert_eq!(node.rect.min, Vec2::ZERO);
+            assert_near(node.rect.max, image_size);
+            assert_eq!(node.atlas_size, None);
+            assert_eq!(node.clip, None);
+            assert_eq!(node.background_color, COLOR);
+            assert_eq!(node.stack_index, 0);
+            assert_eq!(node.scale_factor, 1.);
+            assert_eq!(node.image, texture);
+        }
+    }
+}
+
+#[test]
+fn stack_index_follows_ui_stack() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(TEXTURE_SIZE.0, TEXTURE_SIZE.1));
+    let colors = [Color::RED, Color::GREEN];
+    harness
+        .app
+        .world
+        .spawn(NodeBundle {
+            style: node_style(Vec2::ZERO, WINDOW_SIZE),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for (i, color) in colors.into_iter().enumerate() {
+                parent.spawn(ExactImageBundle {
+                    image: ExactImage {
+                        texture: texture.clone(),
+                        color,
+                        ..Default::default()
+                    },
+                    style: node_style(Vec2::splat(10. * i as f32), NODE_SIZE),
+                    ..Default::default()
+                });
+            }
+        });
+    harness.update();
+
+    let nodes = harness.extracted_nodes();
+    assert_eq!(nodes.len(), 2);
+    // the transparent parent is first in the stack but isn't drawn
+    assert_eq!(nodes[0].stack_index, 1);
+    assert_eq!(nodes[0].background_color, colors[0]);
+    assert_eq!(nodes[1].stack_index, 2);
+    assert_eq!(nodes[1].background_color, colors[1]);
+}
+
+#[test]
+fn clip_is_taken_from_overflow_hidden_parent() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(TEXTURE_SIZE.0, TEXTURE_SIZE.1));
+    harness
+        .app
+        .world
+        .spawn(NodeBundle {
+            style: Style {
+                overflow: Overflow::Hidden,
+                ..node_style(Vec2::new(20., 30.), Vec2::new(100., 60.))
+            },
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn(ExactImageBundle {
+                image: ExactImage {
+                    texture,
+                    ..Default::default()
+                },
+                style: node_style(Vec2::ZERO, NODE_SIZE),
+                ..Default::default()
+            });
+        });
+    harness.update();
+
+    let nodes = harness.extracted_nodes();
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].clip, Some(Rect::new(20., 30., 120., 90.)));
+}
+
+#[test]
+fn rotation_turns_the_image_around_its_center() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(TEXTURE_SIZE.0, TEXTURE_SIZE.1));
+    spawn_image(
+        &mut harness,
+        ExactImage {
+            texture,
+            size: ExactSize::Texture,
+            alignment: ImageAlignment::TopLeft,
+            rotation: Some(FRAC_PI_2),
+            ..Default::default()
+        },
+    );
+    harness.update();
+
+    let node = &harness.extracted_nodes()[0];
+    let center = NODE_CENTER + expected_offset(ImageAlignment::TopLeft, Vec2::new(32., 16.));
+    assert_near(translation(node), center);
+    assert_near(
+        node.transform.transform_vector3(Vec3::X).truncate(),
+        Vec2::Y,
+    );
+}
+
+#[test]
+fn atlas_frame_is_drawn_from_its_rect() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(TEXTURE_SIZE.0, TEXTURE_SIZE.1));
+    let atlas = harness.add_atlas(TextureAtlas::from_grid(
+        texture.clone(),
+        Vec2::new(16., 8.),
+        2,
+        2,
+        None,
+        None,
+    ));
+    harness.spawn(ExactAtlasImageBundle {
+        image: ExactAtlasImage {
+            atlas,
+            index: 3,
+            size: ExactSize::Scaled(Vec2::splat(2.)),
+            alignment: ImageAlignment::BottomRight,
+            ..Default::default()
+        },
+        style: node_style(NODE_POSITION, NODE_SIZE),
+        ..Default::default()
+    });
+    harness.update();
+
+    let nodes = harness.extracted_nodes();
+    assert_eq!(nodes.len(), 1);
+    let node = &nodes[0];
+    assert_eq!(node.rect, Rect::new(16., 8., 32., 16.));
+    assert_eq!(node.atlas_size, Some(Vec2::new(32., 16.)));
+    assert_eq!(node.image, texture);
+    assert_near(
+        translation(node),
+        NODE_CENTER + expected_offset(ImageAlignment::BottomRight, Vec2::new(32., 16.)),
+    );
+    assert_near(
+        node.transform.transform_vector3(Vec3::ONE).truncate(),
+        Vec2::splat(2.),
+    );
+}
+
+#[test]
+fn hidden_and_transparent_images_are_not_extracted() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(TEXTURE_SIZE.0, TEXTURE_SIZE.1));
+    harness.spawn(ExactImageBundle {
+        image: ExactImage {
+            texture: texture.clone(),
+            ..Default::default()
+        },
+        style: node_style(NODE_POSITION, NODE_SIZE),
+        visibility: Visibility::INVISIBLE,
+        ..Default::default()
+    });
+    spawn_image(
+        &mut harness,
+        ExactImage {
+            texture,
+            color: Color::NONE,
+            ..Default::default()
+        },
+    );
+    harness.update();
+
+    assert!(harness.extracted_nodes().is_empty());
+    assert!(harness.extracted_exact_images().is_empty());
+}
+
+#[test]
+fn blended_images_go_to_the_exact_image_renderer() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(TEXTURE_SIZE.0, TEXTURE_SIZE.1));
+    spawn_image(
+        &mut harness,
+        ExactImage {
+            texture,
+            blend_mode: ImageBlendMode::Additive,
+            ..Default::default()
+        },
+    );
+    harness.update();
+
+    assert!(harness.extracted_nodes().is_empty());
+    let images = harness.extracted_exact_images();
+    assert_eq!(images.len(), 1);
+    assert!(images[0].blend_mode == ImageBlendMode::Additive);
+    assert_near(translation(&images[0].node), NODE_CENTER);
+}
+
+#[test]
+fn blended_images_are_drawn_in_stack_order_with_the_rest_of_the_ui() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(TEXTURE_SIZE.0, TEXTURE_SIZE.1));
+    for blend_mode in [
+        ImageBlendMode::Alpha,
+        ImageBlendMode::Additive,
+        ImageBlendMode::Alpha,
+    ] {
+        spawn_image(
+            &mut harness,
+            ExactImage::new(texture.clone()).blend_mode(blend_mode),
+        );
+    }
+    harness.update();
+
+    // the blended image leaves a marker between the others, so bevy doesn't batch them together
+    let nodes = harness.all_extracted_nodes();
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(
+        nodes[1].image,
+        EXACT_IMAGE_MARKER_HANDLE.typed_weak::<Image>()
+    );
+    let images = harness.extracted_exact_images();
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].order, 1);
+    assert!(nodes[0].transform.w_axis.z < images[0].z);
+    assert!(images[0].z < nodes[2].transform.w_axis.z);
+}
+
+#[test]
+fn layers_are_drawn_over_the_image_of_their_node() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(TEXTURE_SIZE.0, TEXTURE_SIZE.1));
+    let icon = harness.add_image(image(8, 8));
+    let image = spawn_image(
+        &mut harness,
+        ExactImage::new(texture).blend_mode(ImageBlendMode::Additive),
+    );
+    harness
+        .app
+        .world
+        .entity_mut(image)
+        .insert(ExactImageLayers::new([ImageLayer {
+            image: icon.clone().into(),
+            size: ExactSize::Texture,
+            color: COLOR,
+            ..Default::default()
+        }]));
+    harness.update();
+
+    let layers = harness.extracted_nodes();
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].image, icon);
+    assert_eq!(layers[0].rect.size(), Vec2::splat(8.));
+    assert_near(translation(layers[0]), NODE_CENTER);
+    let images = harness.extracted_exact_images();
+    assert_eq!(images.len(), 1);
+    assert!(images[0].z < layers[0].transform.w_axis.z);
+}
+
+#[test]
+fn images_with_a_sampling_override_go_to_the_exact_image_renderer() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(TEXTURE_SIZE.0, TEXTURE_SIZE.1));
+    for (i, sampling) in [ImageSampling::Nearest, ImageSampling::LinearMipmapped]
+        .into_iter()
+        .enumerate()
+    {
+        harness.spawn((
+            ExactImageBundle {
+                image: ExactImage::new(texture.clone()),
+                style: node_style(NODE_POSITION + 10. * i as f32, NODE_SIZE),
+                ..Default::default()
+            },
+            sampling,
+        ));
+    }
+    spawn_image(&mut harness, ExactImage::new(texture));
+    harness.update();
+
+    assert_eq!(harness.extracted_nodes().len(), 1);
+    assert_eq!(harness.extracted_nodes()[0].stack_index, 2);
+    let images = harness.extracted_exact_images();
+    assert_eq!(images.len(), 2);
+    assert_eq!(images[0].sampling, Some(ImageSampling::Nearest));
+    assert_eq!(images[1].sampling, Some(ImageSampling::LinearMipmapped));
+}
+
+#[test]
+fn debug_overlay_outlines_images_unless_disabled() {
+    let mut harness = Harness::new();
+    harness.app.add_plugin(ExactImageDebugPlugin);
+    let texture = harness.add_image(image(TEXTURE_SIZE.0, TEXTURE_SIZE.1));
+    let entity = spawn_image(
+        &mut harness,
+        ExactImage {
+            texture,
+            size: ExactSize::Texture,
+            ..Default::default()
+        },
+    );
+    harness.update();
+
+    // the image, the outlines of the node and image, and the anchor and pivot markers
+    let nodes = harness.extracted_nodes();
+    assert_eq!(nodes.len(), 1 + 4 + 4 + 2 + 2);
+    let image_outline: Vec<_> = nodes
+        .iter()
+        .filter(|node| node.background_color == Color::CYAN)
+        .collect();
+    assert_eq!(image_outline.len(), 4);
+    for edge in image_outline {
+        assert_near(
+            (translation(edge) - NODE_CENTER).abs(),
+            match edge.rect.max.x < edge.rect.max.y {
+                true => Vec2::new(16., 0.),
+                false => Vec2::new(0., 8.),
+            },
+        );
+    }
+
+    harness
+        .app
+        .world
+        .entity_mut(entity)
+        .insert(ExactImageDebug { enabled: false });
+    harness.update();
+    assert_eq!(harness.extracted_nodes().len(), 1);
+}
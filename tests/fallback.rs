@@ -0,0 +1,170 @@
+mod common;
+
+use bevy::asset::HandleId;
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use bevy_ui_exact_image::diagnostics::ExactImageProblem;
+use bevy_ui_exact_image::prelude::*;
+use common::*;
+
+const NODE_SIZE: Vec2 = Vec2::new(40., 30.);
+
+const MODES: [InvalidImageFallback; 3] = [
+    InvalidImageFallback::Skip,
+    InvalidImageFallback::Placeholder,
+    InvalidImageFallback::Clamp,
+];
+
+/// A 4x4 grid of 16x16 frames
+fn add_atlas(harness: &mut Harness) -> Handle<TextureAtlas> {
+    let texture = harness.add_image(image(64, 64));
+    harness.add_atlas(TextureAtlas::from_grid(
+        texture,
+        Vec2::splat(16.),
+        4,
+        4,
+        None,
+        None,
+    ))
+}
+
+fn harness(fallback: InvalidImageFallback) -> Harness {
+    let mut harness = Harness::new();
+    harness.app.insert_resource(fallback);
+    harness
+}
+
+fn spawn_atlas_image(harness: &mut Harness, atlas: Handle<TextureAtlas>, index: usize) -> Entity {
+    harness.spawn(ExactAtlasImageBundle {
+        image: ExactAtlasImage {
+            atlas,
+            index,
+            ..Default::default()
+        },
+        style: node_style(Vec2::ZERO, NODE_SIZE),
+        ..Default::default()
+    })
+}
+
+fn spawn_atlas_layer(harness: &mut Harness, atlas: Handle<TextureAtlas>, index: usize) -> Entity {
+    harness.spawn((
+        NodeBundle {
+            style: node_style(Vec2::ZERO, NODE_SIZE),
+            background_color: Color::NONE.into(),
+            ..Default::default()
+        },
+        ExactImageLayers::new([ImageLayer {
+            image: LayerImage::Atlas { atlas, index },
+            ..Default::default()
+        }]),
+    ))
+}
+
+/// The problems warned about since the reader last read them
+fn warnings(
+    harness: &Harness,
+    reader: &mut ManualEventReader<ExactImageWarning>,
+) -> Vec<(Entity, ExactImageProblem)> {
+    let events = harness.app.world.resource::<Events<ExactImageWarning>>();
+    reader
+        .iter(events)
+        .map(|warning| (warning.entity, warning.problem.clone()))
+        .collect()
+}
+
+/// The color and source rect of each node extracted in the last frame
+fn drawn(harness: &Harness) -> Vec<(Color, Rect)> {
+    harness
+        .extracted_nodes()
+        .into_iter()
+        .filter(|node| node.background_color != Color::NONE)
+        .map(|node| (node.background_color, node.rect))
+        .collect()
+}
+
+/// What each fallback draws in place of a frame beyond the end of the atlas
+fn expected(fallback: InvalidImageFallback) -> Vec<(Color, Rect)> {
+    match fallback {
+        InvalidImageFallback::Skip => vec![],
+        InvalidImageFallback::Placeholder => vec![(Color::FUCHSIA, Rect::new(0., 0., 40., 30.))],
+        InvalidImageFallback::Clamp => vec![(Color::WHITE, Rect::new(48., 48., 64., 64.))],
+    }
+}
+
+#[test]
+fn out_of_range_indices_are_drawn_by_the_fallback_and_warned_about() {
+    for fallback in MODES {
+        let mut harness = harness(fallback);
+        let mut reader = ManualEventReader::default();
+        let atlas = add_atlas(&mut harness);
+        let entity = spawn_atlas_image(&mut harness, atlas, 20);
+        harness.update();
+        assert_eq!(drawn(&harness), expected(fallback), "{fallback:?}");
+        let problem = ExactImageProblem::AtlasIndexOutOfRange {
+            layer: None,
+            index: 20,
+            frames: 16,
+        };
+        assert_eq!(warnings(&harness, &mut reader), [(entity, problem)]);
+    }
+}
+
+#[test]
+fn out_of_range_layers_are_drawn_by_the_fallback_and_warned_about() {
+    for fallback in MODES {
+        let mut harness = harness(fallback);
+        let mut reader = ManualEventReader::default();
+        let atlas = add_atlas(&mut harness);
+        let entity = spawn_atlas_layer(&mut harness, atlas, 20);
+        harness.update();
+        assert_eq!(drawn(&harness), expected(fallback), "{fallback:?}");
+        let problem = ExactImageProblem::AtlasIndexOutOfRange {
+            layer: Some(0),
+            index: 20,
+            frames: 16,
+        };
+        assert_eq!(warnings(&harness, &mut reader), [(entity, problem)]);
+    }
+}
+
+#[test]
+fn images_with_a_missing_atlas_are_not_drawn_or_warned_about() {
+    for fallback in MODES {
+        let mut harness = harness(fallback);
+        let mut reader = ManualEventReader::default();
+        let atlas = Handle::weak(HandleId::random::<TextureAtlas>());
+        spawn_atlas_image(&mut harness, atlas.clone(), 20);
+        spawn_atlas_layer(&mut harness, atlas, 20);
+        harness.update();
+        assert_eq!(drawn(&harness), [], "{fallback:?}");
+        assert_eq!(warnings(&harness, &mut reader), []);
+    }
+}
+
+#[test]
+fn fixed_indices_are_warned_about_again_when_they_become_invalid() {
+    let mut harness = harness(InvalidImageFallback::Skip);
+    let mut reader = ManualEventReader::default();
+    let atlas = add_atlas(&mut harness);
+    let entity = spawn_atlas_image(&mut harness, atlas, 20);
+    harness.update();
+    assert_eq!(warnings(&harness, &mut reader).len(), 1);
+
+    let set_index = |harness: &mut Harness, index| {
+        harness
+            .app
+            .world
+            .get_mut::<ExactAtlasImage>(entity)
+            .unwrap()
+            .index = index;
+        harness.update();
+    };
+    set_index(&mut harness, 3);
+    assert_eq!(
+        drawn(&harness),
+        [(Color::WHITE, Rect::new(48., 0., 64., 16.))]
+    );
+    set_index(&mut harness, 21);
+    assert_eq!(drawn(&harness), []);
+    assert_eq!(warnings(&harness, &mut reader).len(), 1);
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+use bevy_ui_exact_image::reload::mark_modified_exact_images;
+use common::*;
+
+/// Images and layers marked as changed since the last clear
+#[derive(Resource, Default)]
+struct ChangedImages(Vec<Entity>);
+
+#[allow(clippy::type_complexity)]
+fn record_changed_images(
+    mut changed: ResMut<ChangedImages>,
+    images: Query<Entity, Or<(Changed<ExactSourceImage>, Changed<ExactImageLayers>)>>,
+) {
+    changed.0.extend(images.iter());
+}
+
+#[test]
+fn resized_texture_resizes_node() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(32, 16));
+    let entity = harness.spawn(ExactImageBundle {
+        image: ExactImage {
+            texture: texture.clone(),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    harness.update();
+    assert_eq!(
+        harness.calculated_size(entity),
+        Some(Size::new(Val::Px(32.), Val::Px(16.)))
+    );
+
+    *harness
+        .app
+        .world
+        .resource_mut::<Assets<Image>>()
+        .get_mut(&texture)
+        .unwrap() = image(64, 48);
+    harness.update();
+    harness.update();
+    assert_eq!(
+        harness.calculated_size(entity),
+        Some(Size::new(Val::Px(64.), Val::Px(48.)))
+    );
+}
+
+#[test]
+fn resized_atlas_resizes_node() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(64, 64));
+    let atlas = harness.add_atlas(TextureAtlas::from_grid(
+        texture.clone(),
+        Vec2::splat(32.),
+        2,
+        2,
+        None,
+        None,
+    ));
+    let entity = harness.spawn(ExactAtlasImageBundle {
+        image: ExactAtlasImage {
+            atlas: atlas.clone(),
+            index: 3,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    harness.update();
+    assert_eq!(
+        harness.calculated_size(entity),
+        Some(Size::new(Val::Px(32.), Val::Px(32.)))
+    );
+
+    *harness
+        .app
+        .world
+        .resource_mut::<Assets<TextureAtlas>>()
+        .get_mut(&atlas)
+        .unwrap() = TextureAtlas::from_grid(texture, Vec2::new(16., 8.), 4, 8, None, None);
+    harness.update();
+    harness.update();
+    assert_eq!(
+        harness.calculated_size(entity),
+        Some(Size::new(Val::Px(16.), Val::Px(8.)))
+    );
+}
+
+#[test]
+fn shrunk_atlas_reports_invalid_index() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(64, 64));
+    let atlas = harness.add_atlas(TextureAtlas::from_grid(
+        texture.clone(),
+        Vec2::splat(16.),
+        4,
+        4,
+        None,
+        None,
+    ));
+    let entity = harness.spawn(ExactAtlasImageBundle {
+        image: ExactAtlasImage {
+            atlas: atlas.clone(),
+            index: 7,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    harness.update();
+    assert!(harness
+        .app
+        .world
+        .resource::<Events<ExactImageWarning>>()
+        .is_empty());
+
+    *harness
+        .app
+        .world
+        .resource_mut::<Assets<TextureAtlas>>()
+        .get_mut(&atlas)
+        .unwrap() = TextureAtlas::from_grid(texture, Vec2::splat(32.), 2, 2, None, None);
+    harness.update();
+    harness.update();
+    let events = harness.app.world.resource::<Events<ExactImageWarning>>();
+    let mut reader = events.get_reader();
+    let warnings: Vec<_> = reader.iter(events).collect();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].entity, entity);
+}
+
+#[test]
+fn modified_assets_only_mark_the_images_referencing_them() {
+    let mut harness = Harness::new();
+    harness
+        .app
+        .init_resource::<ChangedImages>()
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            record_changed_images.after(mark_modified_exact_images),
+        );
+    let modified = harness.add_image(image(32, 16));
+    let unmodified = harness.add_image(image(32, 16));
+    let spawn_image = |harness: &mut Harness, texture: &Handle<Image>| {
+        harness.spawn(ExactImageBundle {
+            image: ExactImage {
+                texture: texture.clone(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    };
+    let spawn_layer = |harness: &mut Harness, texture: &Handle<Image>| {
+        harness.spawn((
+            NodeBundle::default(),
+            ExactImageLayers::new([ImageLayer {
+                image: texture.clone().into(),
+                ..Default::default()
+            }]),
+        ))
+    };
+    let image_entity = spawn_image(&mut harness, &modified);
+    let layer_entity = spawn_layer(&mut harness, &modified);
+    spawn_image(&mut harness, &unmodified);
+    spawn_layer(&mut harness, &unmodified);
+    harness.update();
+    harness.update();
+    harness.app.world.resource_mut::<ChangedImages>().0.clear();
+
+    harness
+        .app
+        .world
+        .resource_mut::<Assets<Image>>()
+        .get_mut(&modified)
+        .unwrap();
+    harness.update();
+    harness.update();
+    let mut changed = harness.app.world.resource::<ChangedImages>().0.clone();
+    changed.sort();
+    let mut expected = vec![image_entity, layer_entity];
+    expected.sort();
+    assert_eq!(changed, expected);
+}
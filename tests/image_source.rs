@@ -0,0 +1,124 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+use common::*;
+
+const SIZES: [ExactSize; 6] = [
+    ExactSize::AttemptPreserveAspectRatio,
+    ExactSize::ForcePreserveAspectRatio,
+    ExactSize::FillNode,
+    ExactSize::Texture,
+    ExactSize::Scaled(Vec2::new(2., 0.5)),
+    ExactSize::Exactly(Vec2::new(10., 20.)),
+];
+
+#[test]
+fn wrappers_lay_out_like_their_source() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(32, 16));
+    let atlas = harness.add_atlas(TextureAtlas::from_grid(
+        texture.clone(),
+        Vec2::new(32., 16.),
+        1,
+        1,
+        None,
+        None,
+    ));
+    let mut entities = Vec::new();
+    for size in SIZES {
+        let wrapped = harness.spawn(ExactImageBundle {
+            image: ExactImage {
+                texture: texture.clone(),
+                size,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let wrapped_atlas = harness.spawn(ExactAtlasImageBundle {
+            image: ExactAtlasImage {
+                atlas: atlas.clone(),
+                size,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let sources = [
+            ImageSource::Texture(texture.clone()),
+            ImageSource::texture_rect(texture.clone(), Rect::new(0., 0., 32., 16.)),
+            ImageSource::Atlas {
+                atlas: atlas.clone(),
+                index: 0,
+            },
+        ]
+        .map(|source| {
+            harness.spawn(ExactSourceImageBundle {
+                image: ExactSourceImage {
+                    source,
+                    size,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        });
+        entities.push((wrapped, wrapped_atlas, sources));
+    }
+    harness.update();
+
+    for (wrapped, wrapped_atlas, sources) in entities {
+        let expected = harness.calculated_size(wrapped);
+        assert_eq!(harness.calculated_size(wrapped_atlas), expected);
+        for source in sources {
+            assert_eq!(harness.calculated_size(source), expected);
+        }
+    }
+}
+
+#[test]
+fn changed_wrapper_updates_its_source() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(32, 16));
+    let entity = harness.spawn(ExactImageBundle {
+        image: ExactImage {
+            texture,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    harness.update();
+
+    let other = harness.add_image(image(8, 24));
+    harness
+        .app
+        .world
+        .get_mut::<ExactImage>(entity)
+        .unwrap()
+        .texture = other;
+    harness.update();
+    assert_eq!(
+        harness.calculated_size(entity),
+        Some(Size::new(Val::Px(8.), Val::Px(24.)))
+    );
+}
+
+#[test]
+fn texture_rect_sizes_node_before_texture_loads() {
+    let mut harness = Harness::new();
+    let texture = harness
+        .app
+        .world
+        .resource::<AssetServer>()
+        .load::<Image, _>("missing.png");
+    let entity = harness.spawn(ExactSourceImageBundle {
+        image: ExactSourceImage {
+            source: ImageSource::texture_rect(texture, Rect::new(4., 4., 28., 12.)),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    harness.update();
+    assert_eq!(
+        harness.calculated_size(entity),
+        Some(Size::new(Val::Px(24.), Val::Px(8.)))
+    );
+}
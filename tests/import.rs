@@ -0,0 +1,148 @@
+mod common;
+
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use bevy_ui_exact_image::import::AtlasTag;
+use bevy_ui_exact_image::import::ImportedFrame;
+use bevy_ui_exact_image::import::TagDirection;
+use bevy_ui_exact_image::prelude::*;
+use common::*;
+
+fn load_atlas(harness: &mut Harness, path: &str) -> ImportedAtlas {
+    let (handle, state) = harness.load::<ImportedAtlas>(path);
+    assert_eq!(state, LoadState::Loaded, "{path}");
+    harness
+        .app
+        .world
+        .resource::<Assets<ImportedAtlas>>()
+        .get(&handle)
+        .unwrap()
+        .clone()
+}
+
+#[test]
+fn frames_are_imported_from_arrays_and_hashes_alike() {
+    let mut harness = Harness::new();
+    for path in ["array.texturepacker.json", "hash.texturepacker.json"] {
+        let imported = load_atlas(&mut harness, path);
+        let atlases = harness.app.world.resource::<Assets<TextureAtlas>>();
+        let atlas = atlases.get(&imported.atlas).unwrap();
+        assert_eq!(atlas.size, Vec2::new(64., 32.));
+        // in the order they were exported
+        assert_eq!(
+            atlas.textures,
+            [Rect::new(0., 0., 10., 8.), Rect::new(16., 0., 36., 16.)],
+            "{path}"
+        );
+        let names = harness.app.world.resource::<Assets<AtlasFrameNames>>();
+        let names = names.get(&imported.names).unwrap();
+        assert_eq!(names.get("idle.png"), Some(0));
+        assert_eq!(names.get("walk.png"), Some(1));
+        assert_eq!(
+            imported.frames,
+            [
+                // trimmed from its source, with the pivot at the center of its bottom edge
+                ImportedFrame {
+                    source_size: Vec2::new(20., 16.),
+                    trim_offset: Vec2::new(6., 4.),
+                    pivot: Some(Vec2::new(0.5, 1.)),
+                    duration: None,
+                },
+                ImportedFrame {
+                    source_size: Vec2::new(20., 16.),
+                    trim_offset: Vec2::ZERO,
+                    pivot: None,
+                    duration: None,
+                },
+            ],
+            "{path}"
+        );
+        assert!(imported.tags.is_empty());
+    }
+}
+
+#[test]
+fn frame_durations_and_tags_are_imported() {
+    let mut harness = Harness::new();
+    let imported = load_atlas(&mut harness, "tags.aseprite.json");
+    let durations: Vec<_> = imported.frames.iter().map(|frame| frame.duration).collect();
+    assert_eq!(durations, [Some(0.1), Some(0.1), Some(0.2), Some(0.1)]);
+    assert_eq!(
+        imported.tags["step"],
+        AtlasTag {
+            from: 1,
+            to: 2,
+            direction: TagDirection::Forward,
+        }
+    );
+
+    let tag_frames = |name: &str| imported.tags[name].frames();
+    assert_eq!(tag_frames("walk"), [0, 1, 2, 3]);
+    assert_eq!(tag_frames("back"), [3, 2, 1, 0]);
+    assert_eq!(tag_frames("bounce"), [0, 1, 2, 3, 2, 1]);
+    assert_eq!(tag_frames("rebound"), [3, 2, 1, 0, 1, 2]);
+    assert_eq!(tag_frames("step"), [1, 2]);
+}
+
+#[test]
+fn atlases_that_cant_be_drawn_as_exported_fail_to_load() {
+    let mut harness = Harness::new();
+    for path in [
+        // frames rotated by the packer
+        "rotated.texturepacker.json",
+        // a tag ending beyond the last frame
+        "tag_out_of_range.aseprite.json",
+        // a tag direction that isn't exported by Aseprite
+        "tag_direction.aseprite.json",
+    ] {
+        let (_, state) = harness.load::<ImportedAtlas>(path);
+        assert_eq!(state, LoadState::Failed, "{path}");
+    }
+}
+
+#[test]
+fn tags_animate_atlas_sources_and_sprites() {
+    let mut harness = Harness::new();
+    let (frames, state) = harness.load::<ImportedAtlas>("tags.aseprite.json");
+    assert_eq!(state, LoadState::Loaded);
+    let atlas = harness
+        .app
+        .world
+        .resource::<Assets<ImportedAtlas>>()
+        .get(&frames)
+        .unwrap()
+        .atlas
+        .clone();
+    let source = ImageSource::Atlas { atlas, index: 0 };
+    // a quarter of a second into the walk, on its third frame
+    let animation = AtlasTagAnimation {
+        elapsed: 0.25,
+        ..AtlasTagAnimation::new(frames, "walk")
+    };
+    let image = harness.spawn((
+        ExactSourceImageBundle {
+            image: ExactSourceImage {
+                source: source.clone(),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        animation.clone(),
+    ));
+    let sprite = harness.spawn((ExactSprite::new(source), animation));
+    harness.update();
+
+    let atlas_index = |source: &ImageSource| match source {
+        ImageSource::Atlas { index, .. } => *index,
+        _ => panic!("not an atlas source"),
+    };
+    let world = &harness.app.world;
+    assert_eq!(
+        atlas_index(&world.get::<ExactSourceImage>(image).unwrap().source),
+        2
+    );
+    assert_eq!(
+        atlas_index(&world.get::<ExactSprite>(sprite).unwrap().source),
+        2
+    );
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_ui_exact_image::import::ImportedFrame;
+use bevy_ui_exact_image::prelude::*;
+use common::*;
+
+const NODE_POSITION: Vec2 = Vec2::new(100., 50.);
+const NODE_SIZE: Vec2 = Vec2::new(200., 150.);
+
+/// A 10x8 frame trimmed from a 20x16 source, 6 pixels from its left and 4 from its top
+fn trimmed_frame(pivot: Option<Vec2>) -> ImportedFrame {
+    ImportedFrame {
+        source_size: Vec2::new(20., 16.),
+        trim_offset: Vec2::new(6., 4.),
+        pivot,
+        duration: None,
+    }
+}
+
+/// Draws the trimmed frame in a 200x150 node, and returns the single quad extracted
+fn draw_frame(frame: ImportedFrame, alignment: ImageAlignment, rotation: Option<f32>) -> Mat4 {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(32, 32));
+    let mut texture_atlas = TextureAtlas::new_empty(texture, Vec2::splat(32.));
+    texture_atlas.add_texture(Rect::new(0., 0., 10., 8.));
+    let atlas = harness.add_atlas(texture_atlas);
+    let frames = harness
+        .app
+        .world
+        .resource_mut::<Assets<ImportedAtlas>>()
+        .add(ImportedAtlas {
+            atlas: atlas.clone(),
+            names: Default::default(),
+            frames: vec![frame],
+            tags: HashMap::default(),
+        });
+    let mut image = ExactAtlasImage::new(atlas, 0)
+        .size(ExactSize::Texture)
+        .align(alignment)
+        .frames(frames);
+    image.rotation = rotation;
+    harness
+        .spawn(ExactAtlasImageBundle::from(image).with_style(node_style(NODE_POSITION, NODE_SIZE)));
+    harness.update();
+
+    let nodes = harness.extracted_nodes();
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].rect, Rect::new(0., 0., 10., 8.));
+    nodes[0].transform
+}
+
+#[test]
+fn trimmed_frames_are_aligned_as_if_untrimmed() {
+    let transform = draw_frame(trimmed_frame(None), ImageAlignment::TopLeft, None);
+    // the untrimmed frame is centered at (110, 58), and the trimmed frame 1 pixel right of its center
+    assert_near(transform.w_axis.truncate().truncate(), Vec2::new(111., 58.));
+    assert_near(transform.x_axis.truncate().truncate(), Vec2::X);
+}
+
+#[test]
+fn centered_pivots_are_laid_out_like_frames_without_a_pivot() {
+    let alignments = [
+        ImageAlignment::TopLeft,
+        ImageAlignment::Center,
+        ImageAlignment::BottomRight,
+        ImageAlignment::Anchor(Vec2::new(0.25, -0.5)),
+    ];
+    for alignment in alignments {
+        for rotation in [None, Some(0.5)] {
+            let pivoted = draw_frame(trimmed_frame(Some(Vec2::splat(0.5))), alignment, rotation);
+            let unpivoted = draw_frame(trimmed_frame(None), alignment, rotation);
+            assert!(
+                pivoted.abs_diff_eq(unpivoted, 1e-3),
+                "expected {unpivoted}, got {pivoted}"
+            );
+        }
+    }
+}
+
+#[test]
+fn pivots_only_move_the_center_of_rotation() {
+    let pivot = Some(Vec2::ZERO);
+    let transform = draw_frame(trimmed_frame(pivot), ImageAlignment::TopLeft, None);
+    assert_near(transform.w_axis.truncate().truncate(), Vec2::new(111., 58.));
+
+    // rotated a quarter turn around the top left corner of the untrimmed frame, at the node's corner
+    let transform = draw_frame(
+        trimmed_frame(pivot),
+        ImageAlignment::TopLeft,
+        Some(FRAC_PI_2),
+    );
+    assert_near(transform.w_axis.truncate().truncate(), Vec2::new(92., 61.));
+    assert_near(transform.x_axis.truncate().truncate(), Vec2::Y);
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use bevy::asset::HandleId;
+use bevy::ecs::event::Event;
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_ui_exact_image::import::ImportedFrame;
+use bevy_ui_exact_image::prelude::*;
+use common::*;
+
+/// The events sent since the reader last read them, which is at most two frames ago
+fn events<E: Event + Clone>(harness: &Harness, reader: &mut ManualEventReader<E>) -> Vec<E> {
+    let events = harness.app.world.resource::<Events<E>>();
+    reader.iter(events).cloned().collect()
+}
+
+fn spawn_image(harness: &mut Harness, texture: Handle<Image>) -> Entity {
+    harness.spawn(ExactImageBundle {
+        image: ExactImage {
+            texture,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+#[test]
+fn loaded_is_sent_once_per_texture() {
+    let mut harness = Harness::new();
+    let mut loaded = ManualEventReader::<ExactImageLoaded>::default();
+    let id = HandleId::random::<Image>();
+    let entity = spawn_image(&mut harness, Handle::weak(id));
+    harness.update();
+    assert!(events(&harness, &mut loaded).is_empty());
+
+    harness
+        .app
+        .world
+        .resource_mut::<Assets<Image>>()
+        .set_untracked(id, image(32, 16));
+    let mut sent = Vec::new();
+    for _ in 0..3 {
+        harness.update();
+        sent.extend(events(&harness, &mut loaded));
+    }
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].entity, entity);
+    assert_eq!(sent[0].size, Vec2::new(32., 16.));
+
+    // changes that don't replace the texture aren't reported
+    harness
+        .app
+        .world
+        .get_mut::<ExactImage>(entity)
+        .unwrap()
+        .color = Color::RED;
+    harness.update();
+    assert!(events(&harness, &mut loaded).is_empty());
+
+    let other = harness.add_image(image(8, 24));
+    harness
+        .app
+        .world
+        .get_mut::<ExactImage>(entity)
+        .unwrap()
+        .texture = other;
+    harness.update();
+    let sent = events(&harness, &mut loaded);
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].size, Vec2::new(8., 24.));
+}
+
+#[test]
+fn loaded_reports_the_untrimmed_size_of_imported_frames() {
+    let mut harness = Harness::new();
+    let mut loaded = ManualEventReader::<ExactImageLoaded>::default();
+    let texture = harness.add_image(image(32, 32));
+    let mut texture_atlas = TextureAtlas::new_empty(texture, Vec2::splat(32.));
+    texture_atlas.add_texture(Rect::new(0., 0., 10., 8.));
+    let atlas = harness.add_atlas(texture_atlas);
+    // a 10x8 frame trimmed from a 20x16 source
+    let frame = ImportedFrame {
+        source_size: Vec2::new(20., 16.),
+        trim_offset: Vec2::new(6., 4.),
+        pivot: None,
+        duration: None,
+    };
+    let id = HandleId::random::<ImportedAtlas>();
+    let entity = harness.spawn(ExactAtlasImageBundle::from(
+        ExactAtlasImage::new(atlas.clone(), 0).frames(Handle::weak(id)),
+    ));
+    harness.update();
+    // not sent until the frames have loaded
+    assert!(events(&harness, &mut loaded).is_empty());
+
+    harness
+        .app
+        .world
+        .resource_mut::<Assets<ImportedAtlas>>()
+        .set_untracked(
+            id,
+            ImportedAtlas {
+                atlas,
+                names: Default::default(),
+                frames: vec![frame],
+                tags: HashMap::default(),
+            },
+        );
+    let mut sent = Vec::new();
+    for _ in 0..3 {
+        harness.update();
+        sent.extend(events(&harness, &mut loaded));
+    }
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].entity, entity);
+    assert_eq!(sent[0].size, Vec2::new(20., 16.));
+}
+
+#[test]
+fn failed_is_sent_once_per_texture() {
+    let mut harness = Harness::new();
+    let mut loaded = ManualEventReader::<ExactImageLoaded>::default();
+    let mut failed = ManualEventReader::<ExactImageFailed>::default();
+    let (texture, _) = harness.load::<Image>("missing.png");
+    let entity = spawn_image(&mut harness, texture.clone());
+    let mut sent = Vec::new();
+    for _ in 0..3 {
+        harness.update();
+        sent.extend(events(&harness, &mut failed));
+    }
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].entity, entity);
+    assert_eq!(sent[0].handle.id, texture.id());
+    assert!(events(&harness, &mut loaded).is_empty());
+
+    harness
+        .app
+        .world
+        .get_mut::<ExactImage>(entity)
+        .unwrap()
+        .color = Color::RED;
+    harness.update();
+    assert!(events(&harness, &mut failed).is_empty());
+}
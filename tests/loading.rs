@@ -0,0 +1,84 @@
+mod common;
+
+use bevy::asset::HandleId;
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+use common::*;
+
+const PLACEHOLDER_SIZE: Vec2 = Vec2::new(64., 32.);
+
+/// An image sized by its texture, drawn as a purple placeholder until it loads
+fn spawn_loading_image(harness: &mut Harness, texture: Handle<Image>) -> Entity {
+    harness.spawn(ExactSourceImageBundle {
+        image: ExactSourceImage {
+            source: texture.into(),
+            loading_placeholder: Some(LoadingPlaceholder::Color(Color::PURPLE)),
+            placeholder_size: Some(PLACEHOLDER_SIZE),
+            ..Default::default()
+        },
+        style: Style {
+            position_type: PositionType::Absolute,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+fn node_size(harness: &Harness, entity: Entity) -> Vec2 {
+    harness.app.world.get::<Node>(entity).unwrap().size()
+}
+
+#[test]
+fn placeholders_are_drawn_at_the_reserved_size_until_the_texture_loads() {
+    let mut harness = Harness::new();
+    let id = HandleId::random::<Image>();
+    let entity = spawn_loading_image(&mut harness, Handle::weak(id));
+    harness.update();
+    harness.update();
+    assert_eq!(node_size(&harness, entity), PLACEHOLDER_SIZE);
+    let nodes = harness.extracted_nodes();
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].background_color, Color::PURPLE);
+    assert_eq!(nodes[0].rect, Rect::new(0., 0., 64., 32.));
+    assert_near(translation(nodes[0]), 0.5 * PLACEHOLDER_SIZE);
+
+    harness
+        .app
+        .world
+        .resource_mut::<Assets<Image>>()
+        .set_untracked(id, image(64, 32));
+    harness.update();
+    harness.update();
+    // the node doesn't move or change size once the texture has loaded
+    assert_eq!(node_size(&harness, entity), PLACEHOLDER_SIZE);
+    let nodes = harness.extracted_nodes();
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].image, Handle::weak(id));
+    assert_eq!(nodes[0].background_color, Color::WHITE);
+    assert_eq!(nodes[0].rect, Rect::new(0., 0., 64., 32.));
+    assert_near(translation(nodes[0]), 0.5 * PLACEHOLDER_SIZE);
+}
+
+#[test]
+fn placeholders_with_unloaded_textures_draw_nothing() {
+    let mut harness = Harness::new();
+    let texture = Handle::weak(HandleId::random::<Image>());
+    let entity = harness.spawn(ExactSourceImageBundle {
+        image: ExactSourceImage {
+            source: ImageSource::Texture(Handle::weak(HandleId::random::<Image>())),
+            loading_placeholder: Some(LoadingPlaceholder::Texture(texture)),
+            placeholder_size: Some(PLACEHOLDER_SIZE),
+            ..Default::default()
+        },
+        style: Style {
+            position_type: PositionType::Absolute,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    harness.update();
+    harness.update();
+    // the size is still reserved
+    assert_eq!(node_size(&harness, entity), PLACEHOLDER_SIZE);
+    assert!(harness.extracted_nodes().is_empty());
+}
@@ -0,0 +1,255 @@
+mod common;
+
+use bevy::asset::HandleId;
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use bevy_ui_exact_image::diagnostics::ExactImageProblem;
+use bevy_ui_exact_image::prelude::*;
+use common::*;
+
+/// A 4x4 grid of 16x16 frames
+fn add_atlas(harness: &mut Harness) -> Handle<TextureAtlas> {
+    let texture = harness.add_image(image(64, 64));
+    harness.add_atlas(TextureAtlas::from_grid(
+        texture,
+        Vec2::splat(16.),
+        4,
+        4,
+        None,
+        None,
+    ))
+}
+
+fn walk_names() -> AtlasFrameNames {
+    AtlasFrameNames::new([("walk_0", 0), ("walk_1", 5)])
+}
+
+fn spawn_named_frame(harness: &mut Harness, names: Handle<AtlasFrameNames>, name: &str) -> Entity {
+    let atlas = add_atlas(harness);
+    harness.spawn((
+        ExactAtlasImageBundle {
+            image: ExactAtlasImage {
+                atlas,
+                index: 2,
+                ..Default::default()
+            },
+            style: node_style(Vec2::ZERO, Vec2::splat(32.)),
+            ..Default::default()
+        },
+        NamedAtlasFrame::new(names, name),
+    ))
+}
+
+fn index(harness: &Harness, entity: Entity) -> usize {
+    harness
+        .app
+        .world
+        .get::<ExactAtlasImage>(entity)
+        .unwrap()
+        .index
+}
+
+/// The problems warned about since the reader last read them
+fn warnings(
+    harness: &Harness,
+    reader: &mut ManualEventReader<ExactImageWarning>,
+) -> Vec<(Entity, ExactImageProblem)> {
+    let events = harness.app.world.resource::<Events<ExactImageWarning>>();
+    reader
+        .iter(events)
+        .map(|warning| (warning.entity, warning.problem.clone()))
+        .collect()
+}
+
+#[test]
+fn names_resolve_to_the_index_of_their_frame() {
+    let mut harness = Harness::new();
+    let names = harness
+        .app
+        .world
+        .resource_mut::<Assets<AtlasFrameNames>>()
+        .add(walk_names());
+    let entity = spawn_named_frame(&mut harness, names, "walk_1");
+    harness.update();
+    assert_eq!(index(&harness, entity), 5);
+    let nodes = harness.extracted_nodes();
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].rect, Rect::new(16., 16., 32., 32.));
+
+    harness
+        .app
+        .world
+        .get_mut::<NamedAtlasFrame>(entity)
+        .unwrap()
+        .name = "walk_0".to_string();
+    harness.update();
+    assert_eq!(index(&harness, entity), 0);
+}
+
+#[test]
+fn names_resolve_once_their_names_have_loaded() {
+    let mut harness = Harness::new();
+    let id = HandleId::random::<AtlasFrameNames>();
+    let entity = spawn_named_frame(&mut harness, Handle::weak(id), "walk_1");
+    harness.update();
+    assert_eq!(index(&harness, entity), 2);
+
+    harness
+        .app
+        .world
+        .resource_mut::<Assets<AtlasFrameNames>>()
+        .set_untracked(id, walk_names());
+    // asset events are sent at the end of the frame
+    harness.update();
+    harness.update();
+    assert_eq!(index(&harness, entity), 5);
+}
+
+#[test]
+fn missing_names_are_warned_about_once_until_they_resolve() {
+    let mut harness = Harness::new();
+    let mut reader = ManualEventReader::default();
+    let names = harness
+        .app
+        .world
+        .resource_mut::<Assets<AtlasFrameNames>>()
+        .add(walk_names());
+    let entity = spawn_named_frame(&mut harness, names, "run_0");
+    harness.update();
+    harness.update();
+    let missing = ExactImageProblem::MissingFrameName("run_0".to_string());
+    assert_eq!(warnings(&harness, &mut reader), [(entity, missing.clone())]);
+    // the index is kept
+    assert_eq!(index(&harness, entity), 2);
+
+    let set_name = |harness: &mut Harness, name: &str| {
+        harness
+            .app
+            .world
+            .get_mut::<NamedAtlasFrame>(entity)
+            .unwrap()
+            .name = name.to_string();
+        harness.update();
+    };
+    set_name(&mut harness, "walk_1");
+    assert_eq!(index(&harness, entity), 5);
+    assert_eq!(warnings(&harness, &mut reader), []);
+
+    set_name(&mut harness, "run_0");
+    assert_eq!(warnings(&harness, &mut reader), [(entity, missing)]);
+}
+
+#[test]
+fn names_resolve_into_atlas_sources_sprites_and_layers() {
+    let mut harness = Harness::new();
+    let names = harness
+        .app
+        .world
+        .resource_mut::<Assets<AtlasFrameNames>>()
+        .add(walk_names());
+    let atlas = add_atlas(&mut harness);
+    let source_atlas = ImageSource::Atlas {
+        atlas: atlas.clone(),
+        index: 2,
+    };
+    let source = harness.spawn((
+        ExactSourceImageBundle {
+            image: ExactSourceImage {
+                source: source_atlas.clone(),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        NamedAtlasFrame::new(names.clone(), "walk_1"),
+    ));
+    let sprite = harness.spawn((
+        ExactSprite::new(source_atlas),
+        NamedAtlasFrame::new(names.clone(), "walk_1"),
+    ));
+    let layer = |index| ImageLayer {
+        image: LayerImage::Atlas {
+            atlas: atlas.clone(),
+            index,
+        },
+        ..Default::default()
+    };
+    let layers = harness.spawn((
+        NodeBundle::default(),
+        ExactImageLayers::new([layer(2), layer(3)]),
+        NamedAtlasFrame::new(names, "walk_1").layer(1),
+    ));
+    harness.update();
+
+    let atlas_index = |source: &ImageSource| match source {
+        ImageSource::Atlas { index, .. } => *index,
+        _ => panic!("not an atlas source"),
+    };
+    let world = &harness.app.world;
+    assert_eq!(
+        atlas_index(&world.get::<ExactSourceImage>(source).unwrap().source),
+        5
+    );
+    assert_eq!(
+        atlas_index(&world.get::<ExactSprite>(sprite).unwrap().source),
+        5
+    );
+    let layer_indices: Vec<_> = world
+        .get::<ExactImageLayers>(layers)
+        .unwrap()
+        .layers
+        .iter()
+        .map(|layer| match layer.image {
+            LayerImage::Atlas { index, .. } => index,
+            LayerImage::Texture(_) => panic!("not an atlas layer"),
+        })
+        .collect();
+    assert_eq!(layer_indices, [2, 5]);
+}
+
+#[test]
+fn names_without_an_atlas_image_are_warned_about() {
+    let mut harness = Harness::new();
+    let mut reader = ManualEventReader::default();
+    let names = harness
+        .app
+        .world
+        .resource_mut::<Assets<AtlasFrameNames>>()
+        .add(walk_names());
+    let texture = harness.add_image(image(16, 16));
+    let entity = harness.spawn((
+        ExactSourceImageBundle {
+            image: ExactSourceImage {
+                source: texture.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        NamedAtlasFrame::new(names.clone(), "walk_1"),
+    ));
+    let layers = harness.spawn((
+        NodeBundle::default(),
+        ExactImageLayers::default(),
+        NamedAtlasFrame::new(names, "walk_1").layer(0),
+    ));
+    harness.update();
+    harness.update();
+    assert_eq!(
+        warnings(&harness, &mut reader),
+        [
+            (
+                entity,
+                ExactImageProblem::NoAtlasForFrameName {
+                    name: "walk_1".to_string(),
+                    layer: None,
+                }
+            ),
+            (
+                layers,
+                ExactImageProblem::NoAtlasForFrameName {
+                    name: "walk_1".to_string(),
+                    layer: Some(0),
+                }
+            ),
+        ]
+    );
+}
@@ -0,0 +1,196 @@
+#![cfg(feature = "rasterizer")]
+
+mod common;
+
+use std::f32::consts::FRAC_PI_2;
+use std::f32::consts::FRAC_PI_4;
+use std::f32::consts::PI;
+use std::panic;
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+use bevy_ui_exact_image::raster::Raster;
+use bevy_ui_exact_image::raster::BLESS_VAR;
+use common::*;
+
+const WINDOW: Vec2 = Vec2::new(160., 120.);
+
+fn golden(name: &str) -> String {
+    format!("{}/tests/golden/{name}.png", env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Spawns one image in each cell of a grid covering the window
+fn spawn_grid<B: Bundle>(
+    harness: &mut Harness,
+    columns: usize,
+    rows: usize,
+    mut image: impl FnMut(usize, Style) -> B,
+) {
+    let cell = WINDOW / Vec2::new(columns as f32, rows as f32);
+    for row in 0..rows {
+        for column in 0..columns {
+            let position = cell * Vec2::new(column as f32, row as f32) + 2.;
+            let bundle = image(row * columns + column, node_style(position, cell - 4.));
+            harness.spawn(bundle);
+        }
+    }
+}
+
+#[test]
+fn alignment() {
+    let mut harness = Harness::with_window_size(WINDOW);
+    let texture = harness.add_image(quadrant_image(16, 8));
+    let alignments = [
+        ImageAlignment::TopLeft,
+        ImageAlignment::TopCenter,
+        ImageAlignment::TopRight,
+        ImageAlignment::CenterLeft,
+        ImageAlignment::Center,
+        ImageAlignment::CenterRight,
+        ImageAlignment::BottomLeft,
+        ImageAlignment::BottomCenter,
+        ImageAlignment::BottomRight,
+    ];
+    spawn_grid(&mut harness, 3, 3, |i, style| ExactImageBundle {
+        image: ExactImage {
+            texture: texture.clone(),
+            size: ExactSize::Texture,
+            alignment: alignments[i],
+            ..Default::default()
+        },
+        style,
+        background_color: Color::DARK_GRAY.into(),
+        ..Default::default()
+    });
+    harness.update();
+    harness
+        .rasterize()
+        .assert_matches_golden(golden("alignment"), 1);
+}
+
+#[test]
+fn sizes() {
+    let mut harness = Harness::with_window_size(WINDOW);
+    let texture = harness.add_image(quadrant_image(16, 8));
+    let sizes = [
+        ExactSize::AttemptPreserveAspectRatio,
+        ExactSize::ForcePreserveAspectRatio,
+        ExactSize::FillNode,
+        ExactSize::Texture,
+        ExactSize::Scaled(Vec2::new(3., 1.5)),
+        ExactSize::Exactly(Vec2::new(12., 30.)),
+    ];
+    spawn_grid(&mut harness, 3, 2, |i, style| ExactImageBundle {
+        image: ExactImage {
+            texture: texture.clone(),
+            size: sizes[i],
+            ..Default::default()
+        },
+        style,
+        ..Default::default()
+    });
+    harness.update();
+    harness
+        .rasterize()
+        .assert_matches_golden(golden("sizes"), 1);
+}
+
+#[test]
+fn rotation() {
+    let mut harness = Harness::with_window_size(WINDOW);
+    let texture = harness.add_image(quadrant_image(16, 8));
+    let rotations = [0., FRAC_PI_4, FRAC_PI_2, PI];
+    spawn_grid(&mut harness, 2, 2, |i, style| ExactImageBundle {
+        image: ExactImage {
+            texture: texture.clone(),
+            size: ExactSize::Scaled(Vec2::splat(3.)),
+            rotation: Some(rotations[i]),
+            ..Default::default()
+        },
+        style,
+        ..Default::default()
+    });
+    harness.update();
+    harness
+        .rasterize()
+        .assert_matches_golden(golden("rotation"), 1);
+}
+
+#[test]
+fn clipping() {
+    let mut harness = Harness::with_window_size(WINDOW);
+    let texture = harness.add_image(quadrant_image(16, 8));
+    harness
+        .app
+        .world
+        .spawn(NodeBundle {
+            style: Style {
+                overflow: Overflow::Hidden,
+                ..node_style(Vec2::new(40., 30.), Vec2::new(80., 60.))
+            },
+            background_color: Color::DARK_GRAY.into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn(ExactImageBundle {
+                image: ExactImage {
+                    texture,
+                    size: ExactSize::Exactly(Vec2::new(120., 60.)),
+                    rotation: Some(FRAC_PI_4),
+                    ..Default::default()
+                },
+                style: node_style(Vec2::new(-20., 10.), Vec2::new(120., 60.)),
+                ..Default::default()
+            });
+        });
+    harness.update();
+    harness
+        .rasterize()
+        .assert_matches_golden(golden("clipping"), 1);
+}
+
+#[test]
+fn atlas() {
+    let mut harness = Harness::with_window_size(WINDOW);
+    let texture = harness.add_image(quadrant_image(32, 16));
+    let atlas = harness.add_atlas(TextureAtlas::from_grid(
+        texture,
+        Vec2::new(8., 4.),
+        4,
+        4,
+        None,
+        None,
+    ));
+    spawn_grid(&mut harness, 4, 4, |i, style| ExactAtlasImageBundle {
+        image: ExactAtlasImage {
+            atlas: atlas.clone(),
+            index: i,
+            size: ExactSize::Scaled(Vec2::splat(3.)),
+            color: Color::rgba(1., 1., 1., 0.25 + 0.05 * i as f32),
+            ..Default::default()
+        },
+        style,
+        ..Default::default()
+    });
+    harness.update();
+    harness
+        .rasterize()
+        .assert_matches_golden(golden("atlas"), 1);
+}
+
+#[test]
+fn missing_goldens_fail_unless_blessed() {
+    if std::env::var_os(BLESS_VAR).is_some() {
+        return;
+    }
+    let path = format!("{}/missing_golden.png", env!("CARGO_TARGET_TMPDIR"));
+    let _ = std::fs::remove_file(&path);
+    let result = panic::catch_unwind(|| Raster::new(4, 4).assert_matches_golden(&path, 0));
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert!(
+        message.starts_with("golden missing, set EXACT_IMAGE_BLESS"),
+        "{message}"
+    );
+    assert!(!Path::new(&path).exists());
+}
@@ -0,0 +1,157 @@
+use bevy::asset::HandleId;
+use bevy::ecs::entity::EntityMap;
+use bevy::prelude::*;
+use bevy::scene::serde::SceneDeserializer;
+use bevy_ui_exact_image::prelude::*;
+use serde::de::DeserializeSeed;
+
+fn type_registry() -> AppTypeRegistry {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<Shader>()
+        .add_asset::<Image>()
+        .add_asset::<TextureAtlas>()
+        .add_plugin(ExactImagePlugin)
+        .add_plugin(ExactImageDebugPlugin)
+        // registered by the render plugin in a full app
+        .register_type::<Color>();
+    app.world.resource::<AppTypeRegistry>().clone()
+}
+
+fn handle<T: bevy::asset::Asset>() -> Handle<T> {
+    Handle::weak(HandleId::random::<T>())
+}
+
+fn round_trip(world: &World, type_registry: &AppTypeRegistry) -> (World, EntityMap) {
+    let scene = DynamicScene::from_world(world, type_registry);
+    let ron = scene.serialize_ron(type_registry).unwrap();
+    let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+    let scene = SceneDeserializer {
+        type_registry: &type_registry.read(),
+    }
+    .deserialize(&mut deserializer)
+    .unwrap();
+    let mut loaded = World::new();
+    loaded.insert_resource(type_registry.clone());
+    let mut entity_map = EntityMap::default();
+    scene.write_to_world(&mut loaded, &mut entity_map).unwrap();
+    (loaded, entity_map)
+}
+
+/// Every reflected component of every entity must come back equal after a round trip through ron
+fn assert_round_trips(world: World) {
+    let type_registry = type_registry();
+    let (loaded, entity_map) = round_trip(&world, &type_registry);
+    let original = DynamicScene::from_world(&world, &type_registry);
+    let reloaded = DynamicScene::from_world(&loaded, &type_registry);
+    for entity in &original.entities {
+        let mapped = entity_map.get(Entity::from_raw(entity.entity)).unwrap();
+        let reloaded_entity = reloaded
+            .entities
+            .iter()
+            .find(|reloaded_entity| reloaded_entity.entity == mapped.index())
+            .unwrap();
+        assert_eq!(entity.components.len(), reloaded_entity.components.len());
+        for component in &entity.components {
+            let reloaded_component = reloaded_entity
+                .components
+                .iter()
+                .find(|reloaded_component| reloaded_component.type_name() == component.type_name())
+                .unwrap_or_else(|| panic!("{} is missing", component.type_name()));
+            assert_eq!(
+                component.reflect_partial_eq(&**reloaded_component),
+                Some(true),
+                "{} changed",
+                component.type_name()
+            );
+        }
+    }
+}
+
+#[test]
+fn exact_image_round_trips() {
+    let texture = handle::<Image>();
+    let image = ExactImage {
+        texture: texture.clone(),
+        rotation: Some(1.5),
+        alignment: ImageAlignment::Anchor(Vec2::new(0.25, -0.5)),
+        color: Color::rgba(0.1, 0.2, 0.3, 0.4),
+        size: ExactSize::Exactly(Vec2::new(30., 40.)),
+        blend_mode: ImageBlendMode::Additive,
+        loading_placeholder: Some(LoadingPlaceholder::Color(Color::PURPLE)),
+        placeholder_size: Some(Vec2::new(12., 34.)),
+    };
+    let source = ExactSourceImage::from(&image);
+
+    let mut world = World::new();
+    let entity = world
+        .spawn((
+            image,
+            source,
+            ImageEffects {
+                grayscale: 0.5,
+                ..Default::default()
+            },
+            ImageOutline {
+                width: 3.,
+                color: Color::RED,
+            },
+            ImageShadow::default(),
+            ExactImageLayers::new([ImageLayer {
+                image: LayerImage::Texture(handle()),
+                size: ExactSize::Scaled(Vec2::splat(2.)),
+                ..Default::default()
+            }]),
+        ))
+        .id();
+
+    let type_registry = type_registry();
+    let (loaded, entity_map) = round_trip(&world, &type_registry);
+    let loaded_image = loaded
+        .get::<ExactImage>(entity_map.get(entity).unwrap())
+        .unwrap();
+    assert_eq!(loaded_image.texture.id(), texture.id());
+    assert_eq!(loaded_image.rotation, Some(1.5));
+    assert!(matches!(loaded_image.size, ExactSize::Exactly(size) if size == Vec2::new(30., 40.)));
+    assert!(loaded_image.blend_mode == ImageBlendMode::Additive);
+
+    assert_round_trips(world);
+}
+
+#[test]
+fn exact_atlas_image_round_trips() {
+    let mut world = World::new();
+    world.spawn((
+        ExactAtlasImage {
+            atlas: handle(),
+            index: 7,
+            alignment: ImageAlignment::TopRight,
+            size: ExactSize::FillNode,
+            frames: Some(handle()),
+            ..Default::default()
+        },
+        ExactImageDebug { enabled: false },
+    ));
+    world.spawn(NamedAtlasFrame::new(handle(), "walk_0"));
+    world.spawn(AtlasTagAnimation::new(handle(), "walk"));
+    world.spawn(DescriptorAnimation::new("idle"));
+    world.spawn(ExactSourceImage {
+        source: ImageSource::TextureRect {
+            texture: handle(),
+            min: Vec2::new(1., 2.),
+            max: Vec2::new(3., 4.),
+        },
+        ..Default::default()
+    });
+    world.spawn(ExactSourceImage {
+        source: ImageSource::Descriptor {
+            descriptor: handle(),
+            clip: Some("idle".into()),
+            frame: 2,
+        },
+        ..Default::default()
+    });
+
+    assert_round_trips(world);
+}
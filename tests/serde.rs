@@ -0,0 +1,74 @@
+#![cfg(feature = "serde")]
+
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+
+#[test]
+fn image_settings_round_trip_without_handles() {
+    let image = ExactImage {
+        texture: Handle::weak(bevy::asset::HandleId::random::<Image>()),
+        rotation: Some(0.5),
+        alignment: ImageAlignment::Anchor(Vec2::new(0.25, -0.5)),
+        color: Color::rgba(0.1, 0.2, 0.3, 0.4),
+        size: ExactSize::Scaled(Vec2::splat(2.)),
+        blend_mode: ImageBlendMode::Multiply,
+        loading_placeholder: Some(LoadingPlaceholder::Color(Color::GRAY)),
+        placeholder_size: Some(Vec2::new(16., 8.)),
+    };
+    let ron = ron::to_string(&image).unwrap();
+    assert!(!ron.contains("texture"));
+
+    let loaded: ExactImage = ron::from_str(&ron).unwrap();
+    assert!(loaded.texture == Handle::default());
+    assert_eq!(loaded.rotation, Some(0.5));
+    assert!(
+        matches!(loaded.alignment, ImageAlignment::Anchor(anchor) if anchor == Vec2::new(0.25, -0.5))
+    );
+    assert_eq!(loaded.color, image.color);
+    assert!(matches!(loaded.size, ExactSize::Scaled(scale) if scale == Vec2::splat(2.)));
+    assert!(loaded.blend_mode == ImageBlendMode::Multiply);
+    assert!(
+        matches!(loaded.loading_placeholder, Some(LoadingPlaceholder::Color(color)) if color == Color::GRAY)
+    );
+    assert_eq!(loaded.placeholder_size, Some(Vec2::new(16., 8.)));
+}
+
+#[test]
+fn atlas_settings_round_trip_without_handles() {
+    let image = ExactAtlasImage {
+        index: 3,
+        alignment: ImageAlignment::BottomRight,
+        size: ExactSize::FillNode,
+        ..Default::default()
+    };
+    let loaded: ExactAtlasImage = ron::from_str(&ron::to_string(&image).unwrap()).unwrap();
+    assert_eq!(loaded.index, 3);
+    assert!(matches!(loaded.alignment, ImageAlignment::BottomRight));
+    assert!(matches!(loaded.size, ExactSize::FillNode));
+
+    let source = ExactSourceImage {
+        source: ImageSource::texture_rect(Default::default(), Rect::new(1., 2., 3., 4.)),
+        ..Default::default()
+    };
+    let loaded: ExactSourceImage = ron::from_str(&ron::to_string(&source).unwrap()).unwrap();
+    assert!(
+        matches!(loaded.source, ImageSource::TextureRect { min, max, .. } if min == Vec2::new(1., 2.) && max == Vec2::new(3., 4.))
+    );
+}
+
+#[test]
+fn effects_round_trip() {
+    let effects = ImageEffects {
+        grayscale: 1.,
+        hue_rotation: 0.5,
+        ..Default::default()
+    };
+    let outline = ImageOutline {
+        color: Color::BLUE,
+        width: 2.,
+    };
+    let loaded: ImageEffects = ron::from_str(&ron::to_string(&effects).unwrap()).unwrap();
+    assert!(loaded == effects);
+    let loaded: ImageOutline = ron::from_str(&ron::to_string(&outline).unwrap()).unwrap();
+    assert!(loaded == outline);
+}
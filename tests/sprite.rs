@@ -0,0 +1,243 @@
+mod common;
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use bevy::sprite::ExtractedSprite;
+use bevy::utils::HashMap;
+use bevy_ui_exact_image::descriptor::NineSlice;
+use bevy_ui_exact_image::prelude::*;
+use common::*;
+
+const CENTER: Vec2 = Vec2::new(200., 125.);
+const BOX_SIZE: Vec2 = Vec2::new(200., 150.);
+
+fn spawn_sprite(harness: &mut Harness, sprite: ExactSprite) -> Entity {
+    harness.spawn(ExactSpriteBundle {
+        sprite,
+        transform: Transform::from_translation(CENTER.extend(0.)),
+        ..Default::default()
+    })
+}
+
+fn add_descriptor(harness: &mut Harness, descriptor: ExactImageDescriptor) -> ImageSource {
+    harness
+        .app
+        .world
+        .resource_mut::<Assets<ExactImageDescriptor>>()
+        .add(descriptor)
+        .into()
+}
+
+fn extracted_sprite(harness: &Harness) -> &ExtractedSprite {
+    let sprites = harness.extracted_sprites();
+    assert_eq!(sprites.len(), 1);
+    &sprites[0]
+}
+
+#[test]
+fn sprites_are_sized_and_aligned_like_nodes_with_y_up() {
+    let cases = [
+        (
+            ExactSize::Texture,
+            ImageAlignment::TopLeft,
+            Vec2::new(32., 16.),
+            Vec2::new(-84., 67.),
+        ),
+        (
+            ExactSize::ForcePreserveAspectRatio,
+            ImageAlignment::BottomRight,
+            Vec2::new(200., 100.),
+            Vec2::new(0., -25.),
+        ),
+        (
+            ExactSize::FillNode,
+            ImageAlignment::CenterLeft,
+            BOX_SIZE,
+            Vec2::ZERO,
+        ),
+        (
+            ExactSize::Scaled(Vec2::new(2., 0.5)),
+            ImageAlignment::TopCenter,
+            Vec2::new(64., 8.),
+            Vec2::new(0., 71.),
+        ),
+        (
+            ExactSize::Exactly(Vec2::new(10., 20.)),
+            ImageAlignment::Anchor(Vec2::new(0.25, -0.5)),
+            Vec2::new(10., 20.),
+            Vec2::new(50., 37.5),
+        ),
+    ];
+    for (size, alignment, expected_size, expected_offset) in cases {
+        let mut harness = Harness::new();
+        let texture = harness.add_image(image(32, 16));
+        spawn_sprite(
+            &mut harness,
+            ExactSprite::new(texture.clone())
+                .box_size(BOX_SIZE)
+                .size(size)
+                .align(alignment)
+                .color(Color::RED),
+        );
+        harness.update();
+
+        let sprite = extracted_sprite(&harness);
+        assert_near(
+            sprite.transform.translation().truncate(),
+            CENTER + expected_offset,
+        );
+        assert_near(sprite.custom_size.unwrap(), expected_size);
+        assert_eq!(sprite.rect, Some(Rect::new(0., 0., 32., 16.)));
+        assert_eq!(sprite.anchor, Vec2::ZERO);
+        assert_eq!(sprite.color, Color::RED);
+        assert_eq!(sprite.image_handle_id, texture.id());
+    }
+}
+
+#[test]
+fn sprite_without_a_box_takes_the_size_of_its_source() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(32, 16));
+    spawn_sprite(
+        &mut harness,
+        ExactSprite::new(texture).align(ImageAlignment::TopLeft),
+    );
+    harness.update();
+
+    let sprite = extracted_sprite(&harness);
+    assert_near(sprite.transform.translation().truncate(), CENTER);
+    assert_near(sprite.custom_size.unwrap(), Vec2::new(32., 16.));
+}
+
+#[test]
+fn sprite_rotates_clockwise_like_nodes() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(32, 16));
+    spawn_sprite(
+        &mut harness,
+        ExactSprite::new(texture)
+            .box_size(BOX_SIZE)
+            .size(ExactSize::Texture)
+            .rotate(FRAC_PI_2),
+    );
+    harness.update();
+
+    let sprite = extracted_sprite(&harness);
+    let right = sprite.transform.affine().transform_vector3(Vec3::X);
+    assert_near(right.truncate(), Vec2::new(0., -1.));
+    assert_near(sprite.custom_size.unwrap(), Vec2::new(32., 16.));
+}
+
+#[test]
+fn sprite_draws_atlas_frames() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(64, 32));
+    let mut atlas = TextureAtlas::new_empty(texture.clone(), Vec2::new(64., 32.));
+    atlas.add_texture(Rect::new(0., 0., 32., 32.));
+    atlas.add_texture(Rect::new(32., 8., 48., 16.));
+    let atlas = harness.add_atlas(atlas);
+    spawn_sprite(
+        &mut harness,
+        ExactSprite::new(ImageSource::Atlas { atlas, index: 1 })
+            .box_size(BOX_SIZE)
+            .size(ExactSize::Scaled(Vec2::splat(2.))),
+    );
+    harness.update();
+
+    let sprite = extracted_sprite(&harness);
+    assert_eq!(sprite.rect, Some(Rect::new(32., 8., 48., 16.)));
+    assert_near(sprite.custom_size.unwrap(), Vec2::new(32., 16.));
+    assert_eq!(sprite.image_handle_id, texture.id());
+}
+
+#[test]
+fn hidden_sprites_are_not_drawn() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(32, 16));
+    let entity = spawn_sprite(&mut harness, ExactSprite::new(texture));
+    harness
+        .app
+        .world
+        .entity_mut(entity)
+        .insert(Visibility::INVISIBLE);
+    harness.update();
+    assert!(harness.extracted_sprites().is_empty());
+}
+
+#[test]
+fn sprites_take_the_presentation_of_their_descriptor() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(32, 16));
+    let source = add_descriptor(
+        &mut harness,
+        ExactImageDescriptor {
+            texture: texture.clone(),
+            rect: Some(Rect::new(0., 0., 16., 16.)),
+            size: ExactSize::Exactly(Vec2::new(10., 20.)),
+            alignment: ImageAlignment::TopLeft,
+            rotation: None,
+            color: Color::RED,
+            nine_slice: None,
+            clips: HashMap::default(),
+        },
+    );
+    spawn_sprite(
+        &mut harness,
+        ExactSprite::new(source)
+            .box_size(BOX_SIZE)
+            .size(ExactSize::FillNode)
+            .align(ImageAlignment::Center),
+    );
+    harness.update();
+
+    let sprite = extracted_sprite(&harness);
+    assert_near(
+        sprite.transform.translation().truncate(),
+        CENTER + Vec2::new(-95., 65.),
+    );
+    assert_near(sprite.custom_size.unwrap(), Vec2::new(10., 20.));
+    assert_eq!(sprite.rect, Some(Rect::new(0., 0., 16., 16.)));
+    assert_eq!(sprite.color, Color::RED);
+    assert_eq!(sprite.image_handle_id, texture.id());
+}
+
+#[test]
+fn nine_slice_descriptor_sprites_are_drawn_a_sprite_per_slice() {
+    let borders = NineSlice {
+        left: 4.,
+        right: 6.,
+        top: 2.,
+        bottom: 8.,
+    };
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(24, 24));
+    let source = add_descriptor(
+        &mut harness,
+        ExactImageDescriptor {
+            texture,
+            rect: None,
+            size: ExactSize::FillNode,
+            alignment: ImageAlignment::Center,
+            rotation: None,
+            color: Color::WHITE,
+            nine_slice: Some(borders),
+            clips: HashMap::default(),
+        },
+    );
+    spawn_sprite(&mut harness, ExactSprite::new(source).box_size(BOX_SIZE));
+    harness.update();
+
+    let expected: Vec<_> = borders
+        .slices(Rect::new(0., 0., 24., 24.), BOX_SIZE, 1.)
+        .collect();
+    let sprites = harness.extracted_sprites();
+    assert_eq!(sprites.len(), expected.len());
+    for (sprite, (source, target)) in sprites.iter().zip(expected) {
+        assert_eq!(sprite.rect, Some(source));
+        assert_near(sprite.custom_size.unwrap(), target.size());
+        // slice targets are laid out with y down, sprites with y up
+        let center = target.center() * Vec2::new(1., -1.);
+        assert_near(sprite.transform.translation().truncate(), CENTER + center);
+    }
+}
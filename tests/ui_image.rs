@@ -0,0 +1,94 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_ui_exact_image::prelude::*;
+use common::*;
+
+const COLOR: Color = Color::rgba(0.25, 0.5, 0.75, 0.5);
+
+fn image_bundle(texture: Handle<Image>, style: Style) -> ImageBundle {
+    ImageBundle {
+        image: UiImage(texture),
+        style,
+        background_color: COLOR.into(),
+        ..Default::default()
+    }
+}
+
+/// Everything about a drawn node that affects how it looks
+#[derive(Debug, PartialEq)]
+struct Look {
+    transform: Mat4,
+    size: Vec2,
+    color: Color,
+    atlas_size: Option<Vec2>,
+}
+
+/// The single node drawn with the given texture
+fn textured_node(harness: &Harness, texture: &Handle<Image>) -> Look {
+    let mut nodes = harness
+        .extracted_nodes()
+        .into_iter()
+        .filter(|node| node.image.id() == texture.id());
+    let node = nodes.next().expect("the texture isn't drawn");
+    assert!(nodes.next().is_none());
+    Look {
+        transform: node.transform,
+        size: node.rect.size(),
+        color: node.background_color,
+        atlas_size: node.atlas_size,
+    }
+}
+
+#[test]
+fn replaced_ui_images_look_the_same() {
+    let styles = [
+        // sized by its texture
+        Style {
+            size: Size::AUTO,
+            ..node_style(Vec2::new(10., 20.), Vec2::ZERO)
+        },
+        // stretched to fill its node
+        node_style(Vec2::new(100., 50.), Vec2::new(200., 150.)),
+    ];
+    for style in styles {
+        let mut harness = Harness::new();
+        let texture = harness.add_image(image(32, 16));
+        let entity = harness.spawn(image_bundle(texture.clone(), style));
+        harness.update();
+        harness.update();
+        let before = textured_node(&harness, &texture);
+
+        harness.app.world.entity_mut(entity).insert(ReplaceUiImage);
+        harness.update();
+        harness.update();
+        let world = &harness.app.world;
+        assert!(world.get::<UiImage>(entity).is_none());
+        assert!(world.get::<ReplaceUiImage>(entity).is_none());
+        assert_eq!(world.get::<ExactImage>(entity).unwrap().color, COLOR);
+        assert_eq!(world.get::<BackgroundColor>(entity).unwrap().0, Color::NONE);
+        assert_eq!(textured_node(&harness, &texture), before);
+    }
+}
+
+#[test]
+fn converted_bundles_look_the_same() {
+    let mut harness = Harness::new();
+    let texture = harness.add_image(image(32, 16));
+    let style = node_style(Vec2::new(100., 50.), Vec2::new(200., 150.));
+    let ui_image = harness.spawn(image_bundle(texture.clone(), style.clone()));
+    harness.update();
+    harness.update();
+    let before = textured_node(&harness, &texture);
+
+    harness.app.world.despawn(ui_image);
+    harness.spawn(ExactImageBundle::from(image_bundle(texture.clone(), style)));
+    harness.update();
+    harness.update();
+    assert_eq!(textured_node(&harness, &texture), before);
+
+    let image = ExactImage::from(UiImage(texture.clone()));
+    assert!(image.texture == texture);
+    assert!(matches!(image.size, ExactSize::AttemptPreserveAspectRatio));
+    assert_eq!(image.color, Color::WHITE);
+}